@@ -49,7 +49,7 @@ fn main() {
             let rec = revy::RecordingStreamBuilder::new("3d_shapes")
                 .spawn()
                 .unwrap();
-            revy::RerunPlugin { rec }
+            revy::RerunPlugin::new(rec)
         })
         // ===============================================================================
         .add_systems(Startup, setup)
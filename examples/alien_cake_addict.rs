@@ -47,7 +47,7 @@ fn main() {
             let rec = revy::RecordingStreamBuilder::new("alien_cake_addict")
                 .spawn()
                 .unwrap();
-            revy::RerunPlugin { rec }
+            revy::RerunPlugin::new(rec)
         })
         // ===============================================================================
         .init_resource::<Game>()
@@ -77,7 +77,7 @@ fn main() {
             let rec = revy::RecordingStreamBuilder::new("breakout")
                 .spawn()
                 .unwrap();
-            revy::RerunPlugin { rec }
+            revy::RerunPlugin::new(rec)
         })
         // ===============================================================================
         .insert_resource(Score(0))
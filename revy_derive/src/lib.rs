@@ -0,0 +1,182 @@
+//! Derive macros for [revy](https://github.com/rerun-io/revy).
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, spanned::Spanned as _, Data, DeriveInput, Ident, LitStr, Member};
+
+// ---
+
+/// The archetype constructors, indexed by the field they take.
+///
+/// Every other field is passed to the matching `with_<field>` builder method.
+const CONSTRUCTORS: &[(&str, &str)] = &[
+    ("positions", "new"),
+    ("vectors", "from_vectors"),
+    ("half_sizes", "from_half_sizes"),
+];
+
+/// Implements `revy::RerunLog` by mapping fields to the fields of a Rerun archetype.
+///
+/// ```rust,ignore
+/// #[derive(Component, RerunLog)]
+/// #[rerun(archetype = Points3D, suffix = "target")]
+/// struct AiTarget {
+///     #[rerun(positions)]
+///     position: Vec3,
+///     #[rerun(colors)]
+///     color: Color,
+///     #[rerun(radii)]
+///     radius: f32,
+///     #[rerun(labels)]
+///     name: String,
+/// }
+/// ```
+///
+/// Container attributes:
+/// * `archetype = …` (required): the archetype to log, either a path or the name of one of the
+///   archetypes in `rerun::archetypes`.
+/// * `suffix = "…"`: logs the archetype at `<entity_path>/comps/<suffix>` rather than at the entity path
+///   itself.
+///
+/// Field attributes:
+/// * `#[rerun(<field>)]`: the name of the archetype field, e.g. `positions`, `colors`, `radii`,
+///   `labels`, …
+///   Exactly one field must map to `positions`, `vectors` or `half_sizes`, which are used to
+///   construct the archetype.
+/// * `#[rerun(<field>, iter)]`: the field is a collection, each item of which is an instance.
+///
+/// Values are converted using `revy::ToRerun`. Fields without a `rerun` attribute are ignored.
+#[proc_macro_derive(RerunLog, attributes(rerun))]
+pub fn derive_rerun_log(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_rerun_log_impl(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn derive_rerun_log_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let mut archetype: Option<syn::Path> = None;
+    let mut suffix: Option<LitStr> = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("rerun"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("archetype") {
+                archetype = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("suffix") {
+                suffix = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("expected `archetype` or `suffix`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let Some(archetype) = archetype else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "missing `#[rerun(archetype = …)]` attribute",
+        ));
+    };
+    let archetype = if let Some(ident) = archetype.get_ident() {
+        quote!(::revy::external::rerun::archetypes::#ident)
+    } else {
+        quote!(#archetype)
+    };
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`RerunLog` can only be derived for structs",
+        ));
+    };
+
+    let mut constructor: Option<(Ident, TokenStream2)> = None;
+    let mut builders = Vec::new();
+
+    for (index, field) in data.fields.iter().enumerate() {
+        let member = field
+            .ident
+            .clone()
+            .map_or_else(|| Member::Unnamed(index.into()), Member::Named);
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("rerun"))
+        {
+            let mut archetype_field: Option<Ident> = None;
+            let mut iter = false;
+
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("iter") {
+                    iter = true;
+                } else if let Some(ident) = meta.path.get_ident() {
+                    if archetype_field.is_some() {
+                        return Err(meta.error("a field can only map to one archetype field"));
+                    }
+                    archetype_field = Some(ident.clone());
+                } else {
+                    return Err(meta.error("expected the name of an archetype field"));
+                }
+                Ok(())
+            })?;
+
+            let Some(archetype_field) = archetype_field else {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "expected the name of an archetype field",
+                ));
+            };
+
+            let values = if iter {
+                quote!(self.#member.iter().map(::revy::ToRerun::to_rerun))
+            } else {
+                quote!([::revy::ToRerun::to_rerun(&self.#member)])
+            };
+
+            let name = archetype_field.to_string();
+            if let Some((_, ctor)) = CONSTRUCTORS.iter().find(|(field, _)| *field == name) {
+                if constructor.is_some() {
+                    return Err(syn::Error::new(
+                        archetype_field.span(),
+                        "only one of `positions`, `vectors` or `half_sizes` can be specified",
+                    ));
+                }
+                constructor = Some((format_ident!("{ctor}"), values));
+            } else {
+                let method = format_ident!("with_{archetype_field}", span = archetype_field.span());
+                builders.push(quote!(.#method(#values)));
+            }
+        }
+    }
+
+    let Some((constructor, constructor_values)) = constructor else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "one field must map to either `positions`, `vectors` or `half_sizes`",
+        ));
+    };
+
+    let suffix = suffix.map_or_else(|| quote!(None), |suffix| quote!(Some(#suffix)));
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::revy::RerunLog for #ident #ty_generics #where_clause {
+            const SUFFIX: Option<&'static str> = #suffix;
+
+            fn to_rerun_log(
+                &self,
+            ) -> Option<Box<dyn ::revy::external::rerun::AsComponents>> {
+                let archetype = #archetype::#constructor(#constructor_values) #(#builders)*;
+                Some(Box::new(archetype))
+            }
+        }
+    })
+}
@@ -0,0 +1,171 @@
+use bevy::{prelude::*, reflect::GetTypeRegistration, state::state::FreelyMutableState};
+
+use crate::{
+    assets::track_asset_changes,
+    events::{log_reflected_event, system_log_events},
+    states::{system_log_next_state, system_log_state},
+    sync::RerunLogSet,
+    RerunEventLoggerFn, RerunLog, RerunLogger, RerunLoggerChain, RerunLoggerContext,
+    RerunTypedComponentLoggers,
+};
+
+// ---
+
+/// Extends Bevy's [`App`] with opt-in Rerun logging for things that aren't entities.
+pub trait RerunAppExt {
+    /// Logs every `T` event as a [`rerun::TextLog`] at `events/<type_name>`, serialized to RON
+    /// via reflection.
+    ///
+    /// `T` gets registered in the type registry if it wasn't already.
+    fn rerun_log_events<T: Event + Reflect + GetTypeRegistration>(&mut self) -> &mut Self;
+
+    /// Logs every `T` event at `events/<type_name>`, using `logger` to convert it to Rerun data.
+    fn rerun_log_events_with<T: Event>(
+        &mut self,
+        logger: impl RerunEventLoggerFn<T> + 'static,
+    ) -> &mut Self;
+
+    /// Logs the `S` state at `states/<type_name>`.
+    ///
+    /// The current state is logged as a text component every time it changes, while every
+    /// transition is logged as a [`rerun::TextLog`].
+    ///
+    /// Works with regular states, sub-states and computed states alike.
+    fn rerun_log_state<S: States>(&mut self) -> &mut Self;
+
+    /// Like [`Self::rerun_log_state`], but also logs every requested [`NextState`] as a
+    /// [`rerun::TextLog`].
+    ///
+    /// Computed states cannot be requested, and therefore aren't supported.
+    fn rerun_log_state_requests<S: FreelyMutableState>(&mut self) -> &mut Self;
+
+    /// Logs every `C` component using `logger` to convert it to Rerun data, instead of whatever
+    /// [`crate::RerunComponentLoggers`] or [`crate::DefaultRerunComponentLoggers`] have to say.
+    ///
+    /// The logger is keyed by `C`'s [`bevy::ecs::component::ComponentId`] rather than its type
+    /// name, so it keeps working when `C` moves to another module.
+    ///
+    /// Only the builtin logger gets replaced: the ones added with [`Self::add_rerun_logger`] and
+    /// [`Self::rerun_log_reflected`] keep running, see [`RerunLoggerChain`].
+    fn register_rerun_logger<C: Component, D: rerun::AsComponents + 'static>(
+        &mut self,
+        logger: impl Fn(&C, &RerunLoggerContext<'_>) -> Option<D> + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    /// Logs every `C` component using `logger`, instead of whatever
+    /// [`crate::RerunComponentLoggers`] or [`crate::DefaultRerunComponentLoggers`] have to say.
+    ///
+    /// Unlike [`Self::register_rerun_logger`], this accepts any [`RerunLogger`], e.g. one that
+    /// declares its asset dependencies with [`RerunLogger::with_asset_dependencies`].
+    fn register_rerun_component_logger<C: Component>(&mut self, logger: RerunLogger) -> &mut Self;
+
+    /// Logs every `C` component using its [`RerunLog`] implementation.
+    ///
+    /// See [`macro@crate::RerunLog`].
+    fn register_rerun_log<C: Component + RerunLog>(&mut self) -> &mut Self;
+
+    /// Logs every `C` component using `logger` at `<entity_path>/comps/<suffix>`, on top of
+    /// whatever loggers are already configured for it, builtin ones included.
+    ///
+    /// See [`RerunLoggerChain`].
+    fn add_rerun_logger<C: Component, D: rerun::AsComponents + 'static>(
+        &mut self,
+        suffix: &'static str,
+        logger: impl Fn(&C, &RerunLoggerContext<'_>) -> Option<D> + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    /// Also logs every `C` component as RON, on top of whatever loggers are already configured
+    /// for it.
+    fn rerun_log_reflected<C: Component>(&mut self) -> &mut Self;
+
+    /// Never logs the `C` component.
+    fn ignore_rerun_component<C: Component>(&mut self) -> &mut Self;
+
+    /// Keeps track of changes to `A` assets, so that the components whose loggers depend on them
+    /// get logged again whenever they are added or modified.
+    ///
+    /// See [`RerunLogger::with_asset_dependencies`].
+    fn register_rerun_asset<A: Asset>(&mut self) -> &mut Self;
+}
+
+impl RerunAppExt for App {
+    #[inline]
+    fn rerun_log_events<T: Event + Reflect + GetTypeRegistration>(&mut self) -> &mut Self {
+        self.register_type::<T>()
+            .rerun_log_events_with::<T>(log_reflected_event::<T>)
+    }
+
+    fn rerun_log_events_with<T: Event>(
+        &mut self,
+        logger: impl RerunEventLoggerFn<T> + 'static,
+    ) -> &mut Self {
+        self.add_event::<T>()
+            .add_systems(Last, system_log_events(logger).in_set(RerunLogSet))
+    }
+
+    #[inline]
+    fn rerun_log_state<S: States>(&mut self) -> &mut Self {
+        self.add_systems(Last, system_log_state::<S>.in_set(RerunLogSet))
+    }
+
+    #[inline]
+    fn rerun_log_state_requests<S: FreelyMutableState>(&mut self) -> &mut Self {
+        self.rerun_log_state::<S>().add_systems(
+            Last,
+            system_log_next_state::<S>
+                .in_set(RerunLogSet)
+                .before(system_log_state::<S>),
+        )
+    }
+
+    fn register_rerun_logger<C: Component, D: rerun::AsComponents + 'static>(
+        &mut self,
+        logger: impl Fn(&C, &RerunLoggerContext<'_>) -> Option<D> + Send + Sync + 'static,
+    ) -> &mut Self {
+        typed_logger_chain::<C>(self).set_replacement(RerunLogger::typed(logger));
+        self
+    }
+
+    fn register_rerun_component_logger<C: Component>(&mut self, logger: RerunLogger) -> &mut Self {
+        typed_logger_chain::<C>(self).set_replacement(logger);
+        self
+    }
+
+    fn register_rerun_log<C: Component + RerunLog>(&mut self) -> &mut Self {
+        typed_logger_chain::<C>(self).set_replacement(RerunLogger::from_rerun_log::<C>());
+        self
+    }
+
+    fn add_rerun_logger<C: Component, D: rerun::AsComponents + 'static>(
+        &mut self,
+        suffix: &'static str,
+        logger: impl Fn(&C, &RerunLoggerContext<'_>) -> Option<D> + Send + Sync + 'static,
+    ) -> &mut Self {
+        typed_logger_chain::<C>(self).push(RerunLogger::typed(logger).with_suffix(suffix));
+        self
+    }
+
+    fn rerun_log_reflected<C: Component>(&mut self) -> &mut Self {
+        typed_logger_chain::<C>(self).reflected = true;
+        self
+    }
+
+    fn ignore_rerun_component<C: Component>(&mut self) -> &mut Self {
+        *typed_logger_chain::<C>(self) = RerunLoggerChain::disabled();
+        self
+    }
+
+    #[inline]
+    fn register_rerun_asset<A: Asset>(&mut self) -> &mut Self {
+        track_asset_changes::<A>(self);
+        self
+    }
+}
+
+fn typed_logger_chain<C: Component>(app: &mut App) -> Mut<'_, RerunLoggerChain> {
+    let world = app.world_mut();
+    let component_id = world.register_component::<C>();
+    world
+        .get_resource_or_init::<RerunTypedComponentLoggers>()
+        .map_unchanged(|loggers| loggers.entry(component_id).or_default())
+}
@@ -0,0 +1,262 @@
+//! Logs shared assets once, rather than once per entity that uses them.
+//!
+//! * Every triangle mesh is logged under `assets/meshes/<mesh>`, or
+//!   `assets/meshes/<mesh>/<material type>/<material>` if it has a material, along with the
+//!   [`rerun::InstancePoses3D`] of all the entities that use it with that material.
+//!
+//! Textures are embedded in the meshes that use them as albedo, since that's the only way
+//! Rerun's [`rerun::Mesh3D`] can be textured. They aren't logged on their own on top of that,
+//! so that each texture is only stored once per mesh and material pair.
+//!
+//! Assets are (re-)logged when first used, or when they get modified. The instance poses are
+//! re-logged whenever one of the instances moves.
+
+use std::any::TypeId;
+
+use bevy::{
+    asset::UntypedAssetId,
+    ecs::event::EventCursor,
+    prelude::*,
+    render::mesh::PrimitiveTopology,
+    utils::{HashMap, HashSet},
+};
+use rerun::external::re_log::ResultExt as _;
+
+use crate::{default_loggers::mesh_with_material, RerunSyncSet, ToRerun as _};
+
+// ---
+
+/// Which material a mesh is rendered with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum MeshMaterialId {
+    Standard(AssetId<StandardMaterial>),
+    Color(AssetId<ColorMaterial>),
+}
+
+impl MeshMaterialId {
+    pub fn from_entity(entity: EntityRef<'_>) -> Option<Self> {
+        entity
+            .get::<MeshMaterial3d<StandardMaterial>>()
+            .map(|mat| Self::Standard(mat.id()))
+            .or_else(|| {
+                entity
+                    .get::<MeshMaterial2d<ColorMaterial>>()
+                    .map(|mat| Self::Color(mat.id()))
+            })
+    }
+
+    #[inline]
+    pub fn untyped(self) -> UntypedAssetId {
+        match self {
+            Self::Standard(id) => id.untyped(),
+            Self::Color(id) => id.untyped(),
+        }
+    }
+
+    /// The texture the material is rendered with, if any.
+    pub fn texture(self, world: &World) -> Option<AssetId<Image>> {
+        match self {
+            Self::Standard(id) => world
+                .resource::<Assets<StandardMaterial>>()
+                .get(id)
+                .and_then(|mat| mat.base_color_texture.as_ref())
+                .map(Handle::id),
+            Self::Color(id) => world
+                .resource::<Assets<ColorMaterial>>()
+                .get(id)
+                .and_then(|mat| mat.texture.as_ref())
+                .map(Handle::id),
+        }
+    }
+}
+
+/// Identifies a set of instances sharing the same mesh and material.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct MeshInstanceKey {
+    pub mesh: AssetId<Mesh>,
+    pub material: Option<MeshMaterialId>,
+}
+
+impl MeshInstanceKey {
+    #[inline]
+    pub fn new(entity: EntityRef<'_>, mesh: AssetId<Mesh>) -> Self {
+        Self {
+            mesh,
+            material: MeshMaterialId::from_entity(entity),
+        }
+    }
+
+    /// Where the mesh and its instances get logged.
+    pub fn entity_path(&self) -> rerun::EntityPath {
+        let mut parts = vec![
+            "assets".into(),
+            "meshes".into(),
+            asset_path_part(self.mesh.untyped()),
+        ];
+        match self.material {
+            Some(MeshMaterialId::Standard(id)) => {
+                parts.extend(["standard_material".into(), asset_path_part(id.untyped())]);
+            }
+            Some(MeshMaterialId::Color(id)) => {
+                parts.extend(["color_material".into(), asset_path_part(id.untyped())]);
+            }
+            None => {}
+        }
+        rerun::EntityPath::new(parts)
+    }
+}
+
+fn asset_path_part(id: UntypedAssetId) -> rerun::EntityPathPart {
+    match id {
+        UntypedAssetId::Index { index, .. } => index.to_bits().to_string().into(),
+        UntypedAssetId::Uuid { uuid, .. } => uuid.to_string().into(),
+    }
+}
+
+/// Only triangle meshes are instanced: Rerun only applies the first instance pose to lines and
+/// points.
+pub(crate) fn is_instanced(mesh: &Mesh) -> bool {
+    matches!(
+        mesh.primitive_topology(),
+        PrimitiveTopology::TriangleList | PrimitiveTopology::TriangleStrip
+    )
+}
+
+// ---
+
+/// The assets that were added or modified since the last sync.
+#[derive(Resource, Default)]
+pub(crate) struct RerunChangedAssets {
+    pub changed: HashSet<UntypedAssetId>,
+
+    /// The asset types whose changes are being tracked.
+    tracked: HashSet<TypeId>,
+}
+
+/// Starts tracking the changes to assets of type `A`, if that's not the case already.
+pub(crate) fn track_asset_changes<A: Asset>(app: &mut App) {
+    let mut changed_assets = app.world_mut().get_resource_or_init::<RerunChangedAssets>();
+    if changed_assets.tracked.insert(TypeId::of::<A>()) {
+        app.add_systems(
+            Last,
+            collect_asset_changes::<A>
+                .after(bevy::asset::AssetEvents)
+                .before(RerunSyncSet),
+        );
+    }
+}
+
+fn collect_asset_changes<A: Asset>(
+    events: Option<Res<'_, Events<AssetEvent<A>>>>,
+    mut cursor: Local<'_, EventCursor<AssetEvent<A>>>,
+    mut changed_assets: ResMut<'_, RerunChangedAssets>,
+) {
+    let Some(events) = events else {
+        return;
+    };
+    changed_assets
+        .changed
+        .extend(cursor.read(&events).filter_map(|event| match *event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(id.untyped()),
+            AssetEvent::Removed { .. }
+            | AssetEvent::Unused { .. }
+            | AssetEvent::LoadedWithDependencies { .. } => None,
+        }));
+}
+
+/// Keeps track of which assets have been logged so far.
+#[derive(Resource, Default)]
+pub(crate) struct RerunAssetsState {
+    /// The instance poses every mesh was last logged with.
+    instances: HashMap<MeshInstanceKey, Vec<GlobalTransform>>,
+}
+
+/// Logs the assets used by `entity_ids`, as well as where they are instanced.
+///
+/// Assets are only logged if they are new, or part of the `changed` assets, unless
+/// `force_full_sync` is set.
+pub(crate) fn sync_assets(
+    world: &mut World,
+    entity_ids: &[Entity],
+    changed: &HashSet<UntypedAssetId>,
+    rec: &rerun::RecordingStream,
+    force_full_sync: bool,
+) {
+    let _trace = info_span!("sync_assets").entered();
+
+    world.resource_scope(|world, mut state: Mut<'_, RerunAssetsState>| {
+        let state = &mut *state;
+
+        let meshes = world.resource::<Assets<Mesh>>();
+        let mut instances: HashMap<MeshInstanceKey, Vec<GlobalTransform>> = HashMap::default();
+        for &entity_id in entity_ids {
+            let Ok(entity) = world.get_entity(entity_id) else {
+                continue;
+            };
+            let Some(mesh) = entity
+                .get::<Mesh3d>()
+                .map(|mesh| mesh.id())
+                .or_else(|| entity.get::<Mesh2d>().map(|mesh| mesh.id()))
+            else {
+                continue;
+            };
+            if !meshes.get(mesh).is_some_and(is_instanced) {
+                continue;
+            }
+
+            let pose = entity.get::<GlobalTransform>().copied().unwrap_or_default();
+            instances
+                .entry(MeshInstanceKey::new(entity, mesh))
+                .or_default()
+                .push(pose);
+        }
+
+        for (key, poses) in &instances {
+            let texture = key.material.and_then(|material| material.texture(world));
+
+            let material = key.material.map(MeshMaterialId::untyped);
+            let modified = changed.contains(&key.mesh.untyped())
+                || material.is_some_and(|id| changed.contains(&id))
+                || texture.is_some_and(|id| changed.contains(&id.untyped()));
+            let logged_poses = state.instances.get(key);
+
+            let entity_path = key.entity_path();
+            if force_full_sync || modified || logged_poses.is_none() {
+                if let Some(mesh) = meshes.get(key.mesh).and_then(|mesh| mesh.to_rerun()) {
+                    let mesh = mesh_with_material(world, mesh, key.material);
+                    rec.log(entity_path.clone(), &mesh).ok_or_log_error();
+                }
+            }
+
+            if force_full_sync || logged_poses != Some(poses) {
+                rec.log(entity_path, &instance_poses(poses))
+                    .ok_or_log_error();
+            }
+        }
+
+        for key in state.instances.keys() {
+            if !instances.contains_key(key) {
+                rec.log(key.entity_path(), &rerun::Clear::flat())
+                    .ok_or_log_error();
+            }
+        }
+
+        state.instances = instances;
+    });
+}
+
+fn instance_poses(poses: &[GlobalTransform]) -> rerun::InstancePoses3D {
+    let poses = poses
+        .iter()
+        .map(GlobalTransform::to_scale_rotation_translation)
+        .collect::<Vec<_>>();
+
+    rerun::InstancePoses3D::new()
+        .with_translations(
+            poses
+                .iter()
+                .map(|(_, _, translation)| translation.to_rerun()),
+        )
+        .with_quaternions(poses.iter().map(|(_, rotation, _)| rotation.to_rerun()))
+        .with_scales(poses.iter().map(|(scale, _, _)| scale.to_rerun()))
+}
@@ -0,0 +1,331 @@
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::{Arc, Once, Weak},
+    time::Duration,
+};
+
+use bevy::{core::FrameCount, prelude::*};
+use parking_lot::Mutex;
+use rerun::log::LogMsg;
+
+use crate::{sync::RerunSyncState, RerunSyncSet};
+
+// ---
+
+/// How much history is kept around in bug report mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BugReportWindow {
+    /// Keep (at least) the last `N` seconds of simulation time.
+    Duration(Duration),
+
+    /// Keep (at least) the last `N` frames.
+    Frames(u32),
+}
+
+/// Configures the bug report mode of [`crate::RerunPlugin`].
+///
+/// In bug report mode, the recording isn't streamed anywhere: the logged data is instead kept in
+/// a bounded in-memory buffer, and only written to disk as an `.rrd` file when a report is
+/// triggered (see [`RerunBugReport`]).
+///
+/// The buffer is made of segments that each start with a full snapshot of the world.
+/// Old data is evicted one segment at a time, so that the recording always starts with a
+/// complete view of the world, no matter how far back it goes.
+#[derive(Debug, Clone)]
+pub struct BugReportSettings {
+    /// How much history should be kept around.
+    pub window: BugReportWindow,
+
+    /// How many full snapshots of the world are taken per [`Self::window`].
+    ///
+    /// More segments means tighter memory bounds at the cost of more frequent snapshots.
+    pub segments_per_window: u32,
+
+    /// Where the `.rrd` files get written to.
+    pub output_dir: PathBuf,
+
+    /// Triggers a report when pressed, if any.
+    pub hotkey: Option<KeyCode>,
+
+    /// Triggers a report when the app panics.
+    pub on_panic: bool,
+}
+
+impl Default for BugReportSettings {
+    fn default() -> Self {
+        Self {
+            window: BugReportWindow::Duration(Duration::from_secs(30)),
+            segments_per_window: 4,
+            output_dir: ".".into(),
+            hotkey: Some(KeyCode::F12),
+            on_panic: true,
+        }
+    }
+}
+
+/// Send this event to write the contents of the bug report buffer to disk.
+///
+/// Only has an effect if [`crate::RerunPlugin`] runs in bug report mode.
+#[derive(Event, Debug, Clone, Copy, Default)]
+pub struct RerunBugReport;
+
+// ---
+
+struct Segment {
+    frame: u32,
+    elapsed: f64,
+    msgs: Vec<LogMsg>,
+}
+
+#[derive(Default)]
+struct BugReportBufferInner {
+    /// Everything that was logged before the first segment started (store info, static data…).
+    ///
+    /// This is never evicted.
+    preamble: Vec<LogMsg>,
+
+    segments: VecDeque<Segment>,
+}
+
+/// A [`rerun::sink::LogSink`] that accumulates everything into a [`BugReportBuffer`].
+struct BugReportSink(Arc<Mutex<BugReportBufferInner>>);
+
+impl rerun::sink::LogSink for BugReportSink {
+    fn send(&self, msg: LogMsg) {
+        let mut inner = self.0.lock();
+        if let Some(segment) = inner.segments.back_mut() {
+            segment.msgs.push(msg);
+        } else {
+            inner.preamble.push(msg);
+        }
+    }
+
+    #[inline]
+    fn flush_blocking(&self) {}
+}
+
+/// The in-memory ring buffer backing bug report mode.
+#[derive(Resource, Clone)]
+pub(crate) struct BugReportBuffer {
+    inner: Arc<Mutex<BugReportBufferInner>>,
+    rec: rerun::RecordingStream,
+    settings: BugReportSettings,
+}
+
+impl BugReportBuffer {
+    /// Swaps the sink of `rec` for an in-memory ring buffer.
+    pub fn new(rec: rerun::RecordingStream, settings: BugReportSettings) -> Self {
+        let inner: Arc<Mutex<BugReportBufferInner>> = Default::default();
+
+        // NOTE: The store info is sent anew to every sink that gets swapped in, so it always ends
+        // up in the preamble, even if the stream already sent it elsewhere (`spawn`, `save`…).
+        rec.set_sink(Box::new(BugReportSink(inner.clone())));
+        Self {
+            inner,
+            rec,
+            settings,
+        }
+    }
+
+    /// Whether a new segment (and therefore a new snapshot) should be started at that point in
+    /// time.
+    fn wants_new_segment(&self, frame: u32, elapsed: f64) -> bool {
+        let inner = self.inner.lock();
+        let Some(last) = inner.segments.back() else {
+            return true;
+        };
+
+        let segments_per_window = self.settings.segments_per_window.max(1);
+        match self.settings.window {
+            BugReportWindow::Duration(window) => {
+                elapsed - last.elapsed >= window.as_secs_f64() / segments_per_window as f64
+            }
+            BugReportWindow::Frames(window) => {
+                frame.saturating_sub(last.frame) >= (window / segments_per_window).max(1)
+            }
+        }
+    }
+
+    /// Starts a new segment, and evicts all segments that have fallen out of the window.
+    fn start_segment(&self, frame: u32, elapsed: f64) {
+        // Make sure everything that's been logged so far ends up in the previous segment.
+        self.rec.flush_blocking();
+
+        let mut inner = self.inner.lock();
+        inner.segments.push_back(Segment {
+            frame,
+            elapsed,
+            msgs: Vec::new(),
+        });
+
+        // We can only drop the oldest segment if the next one still covers the entire window.
+        while inner.segments.len() > 1 {
+            let next = &inner.segments[1];
+            let is_stale = match self.settings.window {
+                BugReportWindow::Duration(window) => elapsed - next.elapsed >= window.as_secs_f64(),
+                BugReportWindow::Frames(window) => frame.saturating_sub(next.frame) >= window,
+            };
+
+            if !is_stale {
+                break;
+            }
+
+            inner.segments.pop_front();
+        }
+    }
+
+    /// Writes the contents of the buffer to a new `.rrd` file in the output directory.
+    ///
+    /// The buffer is left untouched.
+    pub fn save(&self) -> std::io::Result<PathBuf> {
+        self.rec.flush_blocking();
+
+        let bytes = self.inner.lock().encode()?;
+        write_report(&self.settings.output_dir, &bytes)
+    }
+
+    fn save_and_report(&self) {
+        report_saved(self.save());
+    }
+}
+
+impl BugReportBufferInner {
+    fn encode(&self) -> std::io::Result<Vec<u8>> {
+        rerun::external::re_log_encoding::encoder::encode_ref_as_bytes_local(
+            self.preamble
+                .iter()
+                .chain(self.segments.iter().flat_map(|segment| &segment.msgs))
+                .map(Ok),
+        )
+        .map_err(std::io::Error::other)
+    }
+}
+
+fn write_report(output_dir: &Path, bytes: &[u8]) -> std::io::Result<PathBuf> {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let path = output_dir.join(format!("bug_report_{timestamp}.rrd"));
+
+    std::fs::create_dir_all(output_dir)?;
+    std::fs::write(&path, bytes)?;
+
+    Ok(path)
+}
+
+fn report_saved(result: std::io::Result<PathBuf>) {
+    match result {
+        Ok(path) => info!(?path, "bug report written"),
+        Err(err) => error!(%err, "failed to write bug report"),
+    }
+}
+
+// ---
+
+/// The buffers to write to disk when the app panics, along with their output directory.
+static PANIC_BUFFERS: Mutex<Vec<(Weak<Mutex<BugReportBufferInner>>, PathBuf)>> =
+    Mutex::new(Vec::new());
+
+/// The panic hook is shared by all apps, so it must only ever be installed once.
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+fn save_on_panic(buffer: &BugReportBuffer) {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let previous_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            save_panic_buffers();
+            previous_hook(info);
+        }));
+    });
+
+    let mut buffers = PANIC_BUFFERS.lock();
+    buffers.retain(|(inner, _)| inner.strong_count() > 0);
+    buffers.push((
+        Arc::downgrade(&buffer.inner),
+        buffer.settings.output_dir.clone(),
+    ));
+}
+
+/// Writes all live bug report buffers to disk, as-is.
+///
+/// This runs from within the panic hook, and therefore must never block: the panic might have
+/// been raised while one of these locks was held. Whatever hasn't been flushed to the buffers yet
+/// is lost.
+fn save_panic_buffers() {
+    let Some(buffers) = PANIC_BUFFERS.try_lock() else {
+        return;
+    };
+
+    for (inner, output_dir) in buffers.iter() {
+        let Some(inner) = inner.upgrade() else {
+            continue;
+        };
+        let Some(bytes) = inner.try_lock().map(|inner| inner.encode()) else {
+            error!("bug report buffer is locked, cannot write bug report");
+            continue;
+        };
+        report_saved(bytes.and_then(|bytes| write_report(output_dir, &bytes)));
+    }
+}
+
+// ---
+
+/// Keeps the recording in a bounded in-memory buffer, and only writes it to disk on demand.
+pub(crate) struct RerunBugReportPlugin {
+    pub rec: rerun::RecordingStream,
+    pub settings: BugReportSettings,
+}
+
+impl Plugin for RerunBugReportPlugin {
+    fn build(&self, app: &mut App) {
+        let buffer = BugReportBuffer::new(self.rec.clone(), self.settings.clone());
+
+        if self.settings.on_panic {
+            save_on_panic(&buffer);
+        }
+
+        app.insert_resource(buffer)
+            .add_event::<RerunBugReport>()
+            .add_systems(
+                Last,
+                (
+                    system_start_segment.before(RerunSyncSet),
+                    system_trigger_bug_report.after(RerunSyncSet),
+                ),
+            );
+    }
+}
+
+fn system_start_segment(
+    buffer: Res<'_, BugReportBuffer>,
+    mut state: ResMut<'_, RerunSyncState>,
+    time: Res<'_, Time>,
+    frame: Res<'_, FrameCount>,
+) {
+    let elapsed = time.elapsed_secs_f64();
+    if buffer.wants_new_segment(frame.0, elapsed) {
+        buffer.start_segment(frame.0, elapsed);
+        state.force_full_sync = true;
+    }
+}
+
+fn system_trigger_bug_report(
+    buffer: Res<'_, BugReportBuffer>,
+    mut reports: EventReader<'_, '_, RerunBugReport>,
+    keys: Option<Res<'_, ButtonInput<KeyCode>>>,
+) {
+    let hotkey_pressed = buffer
+        .settings
+        .hotkey
+        .zip(keys)
+        .is_some_and(|(hotkey, keys)| keys.just_pressed(hotkey));
+
+    // NOTE: Several reports in the same frame would all end up with the same contents anyway.
+    let report_requested = reports.read().count() > 0;
+
+    if hotkey_pressed || report_requested {
+        buffer.save_and_report();
+    }
+}
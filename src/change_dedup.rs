@@ -0,0 +1,304 @@
+use std::{
+    any::TypeId,
+    hash::{Hash as _, Hasher as _},
+    sync::Arc,
+};
+
+use bevy::{
+    ecs::component::ComponentInfo,
+    prelude::*,
+    ptr::Ptr,
+    reflect::ReflectFromPtr,
+    utils::{AHasher, HashMap},
+};
+
+// ---
+
+/// A fingerprint of the value of a component at a given point in time, as computed by a
+/// [`ChangeDedup`] strategy.
+///
+/// A component whose fingerprint matches the one from the last time it was logged won't be logged
+/// again.
+#[derive(Debug)]
+pub enum ChangeFingerprint {
+    Hash(u64),
+    Value(Box<dyn PartialReflect>),
+}
+
+impl ChangeFingerprint {
+    /// Whether the two fingerprints can be proven to describe the same value.
+    pub fn matches(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Hash(a), Self::Hash(b)) => a == b,
+            (Self::Value(a), Self::Value(b)) => a.reflect_partial_eq(&**b).unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+/// A strategy to deduplicate changes to components that don't actually change anything.
+///
+/// Bevy's change detection flags a component as changed whenever it is mutably accessed, whether
+/// its value actually changed or not: these strategies are used to filter out the resulting noise.
+///
+/// See [`RerunChangeDedup`] to configure which strategy is used for which component.
+pub trait ChangeDedup: Send + Sync {
+    /// Computes the fingerprint of the current value of `component`, which lives at `ptr`.
+    ///
+    /// `component` can either be an entity's component or a resource.
+    ///
+    /// Returns `None` if the component should be considered changed no matter what.
+    fn fingerprint(
+        &self,
+        world: &World,
+        component: &ComponentInfo,
+        ptr: Ptr<'_>,
+    ) -> Option<ChangeFingerprint>;
+
+    /// Whether the current value of `component`, which lives at `ptr`, can be proven to be the
+    /// same as the one `last` was computed from.
+    ///
+    /// This is checked before a new fingerprint gets computed, which only happens if the value did
+    /// change: strategies can override it to compare against `last` in place, without paying for
+    /// a new fingerprint every frame.
+    fn is_unchanged(
+        &self,
+        world: &World,
+        component: &ComponentInfo,
+        ptr: Ptr<'_>,
+        last: &ChangeFingerprint,
+    ) -> bool {
+        self.fingerprint(world, component, ptr)
+            .is_some_and(|current| current.matches(last))
+    }
+}
+
+/// Never deduplicates anything: every change gets logged.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoDedup;
+
+impl ChangeDedup for NoDedup {
+    #[inline]
+    fn fingerprint(
+        &self,
+        _world: &World,
+        _component: &ComponentInfo,
+        _ptr: Ptr<'_>,
+    ) -> Option<ChangeFingerprint> {
+        None
+    }
+}
+
+/// Deduplicates using [`PartialReflect::reflect_hash`].
+///
+/// Components that don't support reflected hashing are always considered changed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReflectHashDedup;
+
+impl ChangeDedup for ReflectHashDedup {
+    fn fingerprint(
+        &self,
+        world: &World,
+        component: &ComponentInfo,
+        ptr: Ptr<'_>,
+    ) -> Option<ChangeFingerprint> {
+        with_reflected(world, component, ptr, |reflected| {
+            reflected.reflect_hash().map(ChangeFingerprint::Hash)
+        })
+    }
+}
+
+/// Deduplicates by comparing the current value against a clone of the last logged one, using
+/// [`PartialReflect::reflect_partial_eq`].
+///
+/// Components that don't support reflected comparisons are always considered changed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReflectPartialEqDedup;
+
+impl ChangeDedup for ReflectPartialEqDedup {
+    fn fingerprint(
+        &self,
+        world: &World,
+        component: &ComponentInfo,
+        ptr: Ptr<'_>,
+    ) -> Option<ChangeFingerprint> {
+        with_reflected(world, component, ptr, |reflected| {
+            Some(ChangeFingerprint::Value(reflected.clone_value()))
+        })
+    }
+
+    fn is_unchanged(
+        &self,
+        world: &World,
+        component: &ComponentInfo,
+        ptr: Ptr<'_>,
+        last: &ChangeFingerprint,
+    ) -> bool {
+        reflected_matches(world, component, ptr, last)
+    }
+}
+
+/// Uses [`ReflectHashDedup`] when the component supports it, and falls back to
+/// [`ReflectPartialEqDedup`] otherwise.
+///
+/// This is the default strategy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AutoDedup;
+
+impl ChangeDedup for AutoDedup {
+    fn fingerprint(
+        &self,
+        world: &World,
+        component: &ComponentInfo,
+        ptr: Ptr<'_>,
+    ) -> Option<ChangeFingerprint> {
+        with_reflected(world, component, ptr, |reflected| {
+            Some(reflected.reflect_hash().map_or_else(
+                || ChangeFingerprint::Value(reflected.clone_value()),
+                ChangeFingerprint::Hash,
+            ))
+        })
+    }
+
+    fn is_unchanged(
+        &self,
+        world: &World,
+        component: &ComponentInfo,
+        ptr: Ptr<'_>,
+        last: &ChangeFingerprint,
+    ) -> bool {
+        reflected_matches(world, component, ptr, last)
+    }
+}
+
+/// Deduplicates by hashing the raw bytes of a plain-old-data component or resource.
+///
+/// This doesn't require any reflection metadata, and is by far the cheapest strategy.
+/// See [`RerunChangeDedup::with_bytes`].
+pub struct BytesDedup<C>(std::marker::PhantomData<fn() -> C>);
+
+impl<C> Default for BytesDedup<C> {
+    #[inline]
+    fn default() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<C: bytemuck::Pod> ChangeDedup for BytesDedup<C> {
+    fn fingerprint(
+        &self,
+        _world: &World,
+        component: &ComponentInfo,
+        ptr: Ptr<'_>,
+    ) -> Option<ChangeFingerprint> {
+        if component.type_id() != Some(TypeId::of::<C>()) {
+            return None;
+        }
+
+        #[allow(unsafe_code)]
+        // Safety: we just checked that `ptr` points to a `C`.
+        let data = unsafe { ptr.deref::<C>() };
+
+        let mut hasher = AHasher::default();
+        bytemuck::bytes_of(data).hash(&mut hasher);
+        Some(ChangeFingerprint::Hash(hasher.finish()))
+    }
+}
+
+/// Compares the reflected value of `component`, which lives at `ptr`, against `last` in place.
+///
+/// Nothing gets cloned: that only happens once the value is known to have changed.
+fn reflected_matches(
+    world: &World,
+    component: &ComponentInfo,
+    ptr: Ptr<'_>,
+    last: &ChangeFingerprint,
+) -> bool {
+    with_reflected(world, component, ptr, |reflected| match last {
+        ChangeFingerprint::Hash(last) => reflected.reflect_hash().map(|hash| hash == *last),
+        ChangeFingerprint::Value(last) => reflected.reflect_partial_eq(&**last),
+    })
+    .unwrap_or(false)
+}
+
+/// Runs `f` on the reflected value of `component`, which lives at `ptr`.
+///
+/// Returns `None` for components without reflection metadata: there's no way to tell whether
+/// these changed, so they are always considered changed.
+fn with_reflected<R>(
+    world: &World,
+    component: &ComponentInfo,
+    ptr: Ptr<'_>,
+    f: impl FnOnce(&dyn PartialReflect) -> Option<R>,
+) -> Option<R> {
+    let type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = type_registry.read();
+
+    let reflected = component
+        .type_id()
+        .and_then(|tid| type_registry.get(tid))
+        .and_then(|ty| ty.data::<ReflectFromPtr>())
+        .map(|reflect_from_ptr| {
+            #[allow(unsafe_code)]
+            // Safety: the type registry cannot be wrong, surely
+            unsafe {
+                reflect_from_ptr.as_reflect(ptr)
+            }
+        });
+
+    reflected.and_then(|reflected| f(reflected.as_partial_reflect()))
+}
+
+// ---
+
+/// Configures which [`ChangeDedup`] strategy is used for which component.
+///
+/// E.g. use [`BytesDedup`] for `my_game::Velocity` and [`AutoDedup`] for everything else.
+#[derive(Resource, Clone)]
+pub struct RerunChangeDedup {
+    /// The strategy used for all components that don't have a dedicated one.
+    pub default: Arc<dyn ChangeDedup>,
+
+    /// Per-component strategies, keyed by the [`TypeId`] of the component or resource.
+    pub components: HashMap<TypeId, Arc<dyn ChangeDedup>>,
+}
+
+impl Default for RerunChangeDedup {
+    #[inline]
+    fn default() -> Self {
+        Self::new(AutoDedup)
+    }
+}
+
+impl RerunChangeDedup {
+    #[inline]
+    pub fn new(default: impl ChangeDedup + 'static) -> Self {
+        Self {
+            default: Arc::new(default),
+            components: Default::default(),
+        }
+    }
+
+    /// Use `strategy` for `C`, which can either be a component or a resource.
+    #[inline]
+    pub fn with<C: 'static>(mut self, strategy: impl ChangeDedup + 'static) -> Self {
+        self.components
+            .insert(TypeId::of::<C>(), Arc::new(strategy));
+        self
+    }
+
+    /// Use [`BytesDedup`] for `C`, which can either be a component or a resource.
+    #[inline]
+    pub fn with_bytes<C: bytemuck::Pod>(self) -> Self {
+        self.with::<C>(BytesDedup::<C>::default())
+    }
+
+    /// Returns the strategy to be used for `component`.
+    pub fn get(&self, component: &ComponentInfo) -> &dyn ChangeDedup {
+        component
+            .type_id()
+            .and_then(|type_id| self.components.get(&type_id))
+            .unwrap_or(&self.default)
+            .as_ref()
+    }
+}
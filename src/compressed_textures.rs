@@ -0,0 +1,213 @@
+//! CPU decoding of block-compressed textures (BCn, ETC2/EAC, ASTC), which Rerun cannot display
+//! as-is.
+//!
+//! Only available with the `compressed_textures` feature.
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{AstcChannel, Extent3d, TextureDimension, TextureFormat},
+    },
+    utils::HashMap,
+};
+use parking_lot::Mutex;
+
+use crate::ToRerun as _;
+
+// ---
+
+/// Caches the decoded version of every compressed [`Image`], so each texture is only ever
+/// decoded once.
+///
+/// Entries are evicted whenever the matching asset gets modified or removed.
+///
+/// Insert your own before adding the [`crate::RerunPlugin`] to change the size cap.
+#[derive(Resource, Debug)]
+pub struct RerunCompressedTextures {
+    /// Compressed textures with more pixels than this are never decoded, and are logged without
+    /// their texture instead.
+    pub max_pixels: u64,
+
+    cache: Mutex<HashMap<AssetId<Image>, Option<DecodedTexture>>>,
+}
+
+type DecodedTexture = (
+    rerun::components::ImageFormat,
+    rerun::components::ImageBuffer,
+);
+
+impl Default for RerunCompressedTextures {
+    fn default() -> Self {
+        Self::new(4096 * 4096)
+    }
+}
+
+impl RerunCompressedTextures {
+    #[inline]
+    pub fn new(max_pixels: u64) -> Self {
+        Self {
+            max_pixels,
+            cache: Default::default(),
+        }
+    }
+
+    /// Returns the decoded version of `image`, decoding it first if it's not in the cache yet.
+    ///
+    /// Returns `None` if the image is too large or its format is not supported, in which case it
+    /// won't be tried again until it gets modified.
+    pub fn get_or_decode(&self, id: AssetId<Image>, image: &Image) -> Option<DecodedTexture> {
+        if let Some(decoded) = self.cache.lock().get(&id) {
+            return decoded.clone();
+        }
+
+        let pixels = u64::from(image.width()) * u64::from(image.height());
+        let decoded = if pixels > self.max_pixels {
+            warn!(
+                ?id,
+                width = image.width(),
+                height = image.height(),
+                max_pixels = self.max_pixels,
+                "compressed texture is too large to be decoded, skipping"
+            );
+            None
+        } else {
+            // NOTE: Decode outside of the lock, this is called from all sync workers at once.
+            decompress(image).and_then(|image| image.to_rerun())
+        };
+
+        self.cache.lock().insert(id, decoded.clone());
+
+        decoded
+    }
+
+    /// Evicts the decoded version of `id`, if any.
+    #[inline]
+    pub fn evict(&self, id: AssetId<Image>) {
+        self.cache.lock().remove(&id);
+    }
+}
+
+/// Keeps [`RerunCompressedTextures`] in sync with the [`Image`] assets.
+pub(crate) fn evict_compressed_textures(
+    mut events: EventReader<AssetEvent<Image>>,
+    textures: Res<RerunCompressedTextures>,
+) {
+    for event in events.read() {
+        match event {
+            AssetEvent::Modified { id }
+            | AssetEvent::Removed { id }
+            | AssetEvent::Unused { id } => textures.evict(*id),
+            AssetEvent::Added { .. } | AssetEvent::LoadedWithDependencies { .. } => {}
+        }
+    }
+}
+
+// ---
+
+/// Decodes the first mip level of the first layer of a block-compressed `image`.
+///
+/// The result uses an uncompressed format with the same channels and color space:
+/// * BC4 and EAC R11 become `R8Unorm`,
+/// * BC5 and EAC RG11 become `Rg8Unorm`,
+/// * everything else becomes `Bgra8UnormSrgb` or `Bgra8Unorm`.
+///
+/// Returns `None` for uncompressed images, as well as for signed and HDR ASTC formats.
+pub fn decompress(image: &Image) -> Option<Image> {
+    use texture2ddecoder as dec;
+    use TextureFormat as F;
+
+    type DecodeFn<'a> = &'a dyn Fn(&[u8], usize, usize, &mut [u32]) -> Result<(), &'static str>;
+
+    let format = image.texture_descriptor.format;
+    if !format.is_compressed() {
+        return None;
+    }
+
+    let (width, height) = (image.width(), image.height());
+    let (block_width, block_height) = format.block_dimensions();
+    let block_size = format.block_copy_size(None)?;
+
+    let decode_astc = |data: &[u8], width, height, pixels: &mut [u32]| {
+        dec::decode_astc(
+            data,
+            width,
+            height,
+            block_width as _,
+            block_height as _,
+            pixels,
+        )
+    };
+
+    let (decode, output): (DecodeFn<'_>, _) = match format {
+        F::Bc1RgbaUnorm | F::Bc1RgbaUnormSrgb => (&dec::decode_bc1a, Output::Bgra),
+        F::Bc2RgbaUnorm | F::Bc2RgbaUnormSrgb => (&dec::decode_bc2, Output::Bgra),
+        F::Bc3RgbaUnorm | F::Bc3RgbaUnormSrgb => (&dec::decode_bc3, Output::Bgra),
+        F::Bc4RUnorm => (&dec::decode_bc4, Output::R),
+        F::Bc5RgUnorm => (&dec::decode_bc5, Output::Rg),
+        F::Bc6hRgbUfloat => (&dec::decode_bc6_unsigned, Output::Bgra),
+        F::Bc6hRgbFloat => (&dec::decode_bc6_signed, Output::Bgra),
+        F::Bc7RgbaUnorm | F::Bc7RgbaUnormSrgb => (&dec::decode_bc7, Output::Bgra),
+
+        F::Etc2Rgb8Unorm | F::Etc2Rgb8UnormSrgb => (&dec::decode_etc2_rgb, Output::Bgra),
+        F::Etc2Rgb8A1Unorm | F::Etc2Rgb8A1UnormSrgb => (&dec::decode_etc2_rgba1, Output::Bgra),
+        F::Etc2Rgba8Unorm | F::Etc2Rgba8UnormSrgb => (&dec::decode_etc2_rgba8, Output::Bgra),
+        F::EacR11Unorm => (&dec::decode_eacr, Output::R),
+        F::EacRg11Unorm => (&dec::decode_eacrg, Output::Rg),
+
+        F::Astc {
+            channel: AstcChannel::Unorm | AstcChannel::UnormSrgb,
+            ..
+        } => (&decode_astc, Output::Bgra),
+
+        _ => return None,
+    };
+
+    // NOTE: The first mip level of the first layer always comes first, whatever the data order.
+    let size = width.div_ceil(block_width) * height.div_ceil(block_height) * block_size;
+    let data = image.data.get(..size as usize)?;
+
+    let mut pixels = vec![0u32; width as usize * height as usize];
+    if let Err(err) = decode(data, width as _, height as _, &mut pixels) {
+        warn!(?format, err, "failed to decode compressed texture");
+        return None;
+    }
+
+    // NOTE: Decoded pixels are BGRA, in memory order.
+    let bgra = pixels.iter().flat_map(|pixel| pixel.to_le_bytes());
+    let (data, format) = match output {
+        Output::R => (bgra.skip(2).step_by(4).collect(), F::R8Unorm),
+        Output::Rg => (
+            pixels
+                .iter()
+                .flat_map(|pixel| {
+                    let [_b, g, r, _a] = pixel.to_le_bytes();
+                    [r, g]
+                })
+                .collect(),
+            F::Rg8Unorm,
+        ),
+        Output::Bgra if format.is_srgb() => (bgra.collect(), F::Bgra8UnormSrgb),
+        Output::Bgra => (bgra.collect(), F::Bgra8Unorm),
+    };
+
+    Some(Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        format,
+        RenderAssetUsages::default(),
+    ))
+}
+
+/// Which channels of the decoded pixels are meaningful.
+#[derive(Clone, Copy)]
+enum Output {
+    R,
+    Rg,
+    Bgra,
+}
@@ -1,4 +1,11 @@
-use bevy::{math::Vec3A, prelude::*, render::mesh::VertexAttributeValues};
+use bevy::{
+    math::Vec3A,
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+        render_resource::TextureFormat,
+    },
+};
 use itertools::Itertools;
 
 // ---
@@ -8,6 +15,22 @@ pub trait ToRerun<U> {
     fn to_rerun(&self) -> U;
 }
 
+// NOTE: Passthroughs, so that anything rerun already understands can be used with `ToRerun`.
+
+impl ToRerun<f32> for f32 {
+    #[inline]
+    fn to_rerun(&self) -> f32 {
+        *self
+    }
+}
+
+impl ToRerun<String> for String {
+    #[inline]
+    fn to_rerun(&self) -> String {
+        self.clone()
+    }
+}
+
 impl ToRerun<rerun::Vec2D> for Vec2 {
     #[inline]
     fn to_rerun(&self) -> rerun::Vec2D {
@@ -76,53 +99,314 @@ impl ToRerun<rerun::Rgba32> for Color {
     }
 }
 
-impl ToRerun<Option<rerun::archetypes::Mesh3D>> for Mesh {
+/// A [`Mesh`], converted to whichever archetype best matches its [`PrimitiveTopology`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RerunMesh {
+    /// [`PrimitiveTopology::TriangleList`] and [`PrimitiveTopology::TriangleStrip`].
+    Mesh3D(rerun::archetypes::Mesh3D),
+
+    /// [`PrimitiveTopology::LineList`] and [`PrimitiveTopology::LineStrip`].
+    LineStrips3D(rerun::archetypes::LineStrips3D),
+
+    /// [`PrimitiveTopology::PointList`].
+    Points3D(rerun::archetypes::Points3D),
+}
+
+impl rerun::AsComponents for RerunMesh {
     #[inline]
-    fn to_rerun(&self) -> Option<rerun::archetypes::Mesh3D> {
-        if let Some(VertexAttributeValues::Float32x3(positions)) =
-            self.attribute(Mesh::ATTRIBUTE_POSITION)
-        {
-            let mut mesh = rerun::archetypes::Mesh3D::new(positions);
+    fn as_component_batches(&self) -> Vec<rerun::ComponentBatchCowWithDescriptor<'_>> {
+        match self {
+            Self::Mesh3D(mesh) => mesh.as_component_batches(),
+            Self::LineStrips3D(strips) => strips.as_component_batches(),
+            Self::Points3D(points) => points.as_component_batches(),
+        }
+    }
+}
+
+impl ToRerun<Option<RerunMesh>> for Mesh {
+    fn to_rerun(&self) -> Option<RerunMesh> {
+        let positions = self.vertex_positions()?;
+        let positions = positions.as_slice();
+
+        // NOTE: Out of bounds indices are skipped rather than trusted, meshes come from users.
+        let is_valid = |i: u32| (i as usize) < positions.len();
 
-            if let Some(indices) = self.indices() {
-                let indices = indices.iter().map(|i| i as u32).collect_vec();
-                mesh = mesh
-                    .with_triangle_indices(indices.chunks_exact(3).map(|is| [is[0], is[1], is[2]]));
+        let mesh = match self.primitive_topology() {
+            PrimitiveTopology::TriangleList => {
+                let mut mesh = rerun::archetypes::Mesh3D::new(positions);
+                if let Some(indices) = self.indices() {
+                    let indices = indices.iter().map(|i| i as u32).collect_vec();
+                    mesh = mesh.with_triangle_indices(
+                        indices
+                            .chunks_exact(3)
+                            .map(|is| [is[0], is[1], is[2]])
+                            .filter(|triangle| triangle.iter().all(|&i| is_valid(i))),
+                    );
+                }
+                RerunMesh::Mesh3D(self.with_triangle_attributes(mesh))
+            }
+
+            PrimitiveTopology::TriangleStrip => {
+                let triangles = self
+                    .vertex_sequences(positions.len())
+                    .into_iter()
+                    .flat_map(|strip| destrip(&strip))
+                    .filter(|triangle| triangle.iter().all(|&i| is_valid(i)))
+                    .collect_vec();
+                let mesh =
+                    rerun::archetypes::Mesh3D::new(positions).with_triangle_indices(triangles);
+                RerunMesh::Mesh3D(self.with_triangle_attributes(mesh))
+            }
+
+            PrimitiveTopology::LineList => {
+                let strips = self
+                    .vertex_sequences(positions.len())
+                    .into_iter()
+                    .flat_map(|sequence| {
+                        sequence
+                            .chunks_exact(2)
+                            .filter_map(|is| {
+                                Some([
+                                    *positions.get(is[0] as usize)?,
+                                    *positions.get(is[1] as usize)?,
+                                ])
+                            })
+                            .collect_vec()
+                    })
+                    .collect_vec();
+                RerunMesh::LineStrips3D(rerun::archetypes::LineStrips3D::new(strips))
+            }
+
+            PrimitiveTopology::LineStrip => {
+                let strips = self
+                    .vertex_sequences(positions.len())
+                    .into_iter()
+                    .map(|strip| {
+                        strip
+                            .into_iter()
+                            .filter_map(|i| positions.get(i as usize).copied())
+                            .collect_vec()
+                    })
+                    .collect_vec();
+                RerunMesh::LineStrips3D(rerun::archetypes::LineStrips3D::new(strips))
+            }
+
+            PrimitiveTopology::PointList => {
+                let indices = self
+                    .vertex_sequences(positions.len())
+                    .into_iter()
+                    .flatten()
+                    .filter(|&i| is_valid(i))
+                    .collect_vec();
+                let mut points = rerun::archetypes::Points3D::new(
+                    indices.iter().map(|&i| positions[i as usize]),
+                );
+                // NOTE: Colors are all or nothing, so that they can't get out of sync with points.
+                if let Some(colors) = self.vertex_colors().and_then(|colors| {
+                    indices
+                        .iter()
+                        .map(|&i| colors.get(i as usize).copied())
+                        .collect::<Option<Vec<_>>>()
+                }) {
+                    points = points.with_colors(colors);
+                }
+                RerunMesh::Points3D(points)
             }
+        };
+
+        Some(mesh)
+    }
+}
+
+/// Helpers for [`ToRerun<Option<RerunMesh>>`].
+trait MeshExt {
+    /// Returns the vertex indices, in order.
+    ///
+    /// Strip topologies are split at every primitive restart, like the GPU would. List topologies
+    /// don't support primitive restart, and always yield a single sequence.
+    ///
+    /// Non-indexed meshes yield a single sequence that covers all vertices.
+    fn vertex_sequences(&self, num_vertices: usize) -> Vec<Vec<u32>>;
+
+    /// Supports `Float32x2` (with `z = 0`), `Float32x3` and `Float32x4` (ignoring `w`) positions.
+    fn vertex_positions(&self) -> Option<Vec<[f32; 3]>>;
+
+    /// Bevy vertex colors are linear, Rerun's are sRGB.
+    ///
+    /// Supports `Float32x3`, `Float32x4`, `Unorm8x4` and `Uint8x4` colors.
+    fn vertex_colors(&self) -> Option<Vec<rerun::Color>>;
+
+    fn with_triangle_attributes(
+        &self,
+        mesh: rerun::archetypes::Mesh3D,
+    ) -> rerun::archetypes::Mesh3D;
+}
 
-            if let Some(VertexAttributeValues::Float32x3(normals)) =
-                self.attribute(Mesh::ATTRIBUTE_NORMAL)
-            {
-                mesh = mesh.with_vertex_normals(normals);
+impl MeshExt for Mesh {
+    fn vertex_sequences(&self, num_vertices: usize) -> Vec<Vec<u32>> {
+        let Some(indices) = self.indices() else {
+            return vec![(0..num_vertices as u32).collect()];
+        };
+
+        let is_strip = matches!(
+            self.primitive_topology(),
+            PrimitiveTopology::LineStrip | PrimitiveTopology::TriangleStrip
+        );
+        if !is_strip {
+            return vec![indices.iter().map(|i| i as u32).collect()];
+        }
+
+        let restart = match indices {
+            Indices::U16(_) => u16::MAX as usize,
+            Indices::U32(_) => u32::MAX as usize,
+        };
+
+        indices
+            .iter()
+            .collect_vec()
+            .split(|&i| i == restart)
+            .filter(|sequence| !sequence.is_empty())
+            .map(|sequence| sequence.iter().map(|&i| i as u32).collect())
+            .collect()
+    }
+
+    fn vertex_positions(&self) -> Option<Vec<[f32; 3]>> {
+        match self.attribute(Mesh::ATTRIBUTE_POSITION)? {
+            VertexAttributeValues::Float32x2(positions) => {
+                Some(positions.iter().map(|&[x, y]| [x, y, 0.0]).collect())
             }
+            VertexAttributeValues::Float32x3(positions) => Some(positions.clone()),
+            VertexAttributeValues::Float32x4(positions) => {
+                Some(positions.iter().map(|&[x, y, z, _]| [x, y, z]).collect())
+            }
+            _ => None,
+        }
+    }
+
+    fn vertex_colors(&self) -> Option<Vec<rerun::Color>> {
+        fn to_srgb(color: LinearRgba) -> rerun::Color {
+            let [r, g, b, a] = Srgba::from(color).to_u8_array();
+            rerun::Color::from_unmultiplied_rgba(r, g, b, a)
+        }
+
+        fn from_u8([r, g, b, a]: [u8; 4]) -> LinearRgba {
+            LinearRgba::from_f32_array([r, g, b, a].map(|c| c as f32 / 255.0))
+        }
 
-            if let Some(VertexAttributeValues::Float32x2(texcoords)) =
-                self.attribute(Mesh::ATTRIBUTE_UV_0)
-            {
-                mesh = mesh.with_vertex_texcoords(texcoords);
+        let colors = match self.attribute(Mesh::ATTRIBUTE_COLOR)? {
+            VertexAttributeValues::Float32x3(colors) => colors
+                .iter()
+                .map(|&rgb| to_srgb(LinearRgba::from_f32_array_no_alpha(rgb)))
+                .collect(),
+            VertexAttributeValues::Float32x4(colors) => colors
+                .iter()
+                .map(|&rgba| to_srgb(LinearRgba::from_f32_array(rgba)))
+                .collect(),
+            VertexAttributeValues::Unorm8x4(colors) | VertexAttributeValues::Uint8x4(colors) => {
+                colors.iter().map(|&rgba| to_srgb(from_u8(rgba))).collect()
             }
+            _ => return None,
+        };
+
+        Some(colors)
+    }
+
+    fn with_triangle_attributes(
+        &self,
+        mut mesh: rerun::archetypes::Mesh3D,
+    ) -> rerun::archetypes::Mesh3D {
+        if let Some(VertexAttributeValues::Float32x3(normals)) =
+            self.attribute(Mesh::ATTRIBUTE_NORMAL)
+        {
+            mesh = mesh.with_vertex_normals(normals);
+        }
+
+        if let Some(VertexAttributeValues::Float32x2(texcoords)) =
+            self.attribute(Mesh::ATTRIBUTE_UV_0)
+        {
+            mesh = mesh.with_vertex_texcoords(texcoords);
+        }
+
+        if let Some(colors) = self.vertex_colors() {
+            mesh = mesh.with_vertex_colors(colors);
+        }
+
+        mesh
+    }
+}
 
-            if let Some(VertexAttributeValues::Float32x4(colors)) =
-                self.attribute(Mesh::ATTRIBUTE_COLOR)
-            {
-                mesh = mesh.with_vertex_colors(colors.iter().map(|[r, g, b, a]| {
-                    // TODO(cmc): is this sRGB? linear? etc?
-                    rerun::Color::from_unmultiplied_rgba(
-                        (r / 255.0) as u8,
-                        (g / 255.0) as u8,
-                        (b / 255.0) as u8,
-                        (a / 255.0) as u8,
-                    )
-                }));
+/// Turns a triangle strip into a list of triangles, preserving winding order and skipping
+/// degenerate triangles.
+fn destrip(strip: &[u32]) -> Vec<[u32; 3]> {
+    strip
+        .windows(3)
+        .enumerate()
+        .map(|(i, is)| {
+            if i % 2 == 0 {
+                [is[0], is[1], is[2]]
+            } else {
+                [is[1], is[0], is[2]]
             }
+        })
+        .filter(|[a, b, c]| a != b && b != c && a != c)
+        .collect()
+}
+
+/// An [`Image`], converted to whichever archetype best matches its [`TextureFormat`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RerunImage {
+    /// Color and single/dual-channel formats.
+    ///
+    /// Dual-channel formats are expanded to RGB, with an empty blue channel.
+    ///
+    /// Rerun expects color to be sRGB-encoded:
+    /// * `*Srgb` formats already are, and are logged as-is.
+    /// * Other 4-channel unorm and float formats (e.g. [`TextureFormat::Rgba8Unorm`],
+    ///   [`TextureFormat::Rgba16Float`]) hold linear color, and are encoded to sRGB first.
+    /// * Single/dual-channel, snorm and integer formats hold data rather than color (heightmaps,
+    ///   masks, …), and are logged as-is.
+    ///
+    /// Array and 3D textures aren't supported.
+    Image(rerun::archetypes::Image),
+
+    /// [`TextureFormat::Depth16Unorm`] and [`TextureFormat::Depth32Float`].
+    DepthImage(rerun::archetypes::DepthImage),
+}
 
-            Some(mesh)
+impl rerun::AsComponents for RerunImage {
+    #[inline]
+    fn as_component_batches(&self) -> Vec<rerun::ComponentBatchCowWithDescriptor<'_>> {
+        match self {
+            Self::Image(image) => image.as_component_batches(),
+            Self::DepthImage(image) => image.as_component_batches(),
+        }
+    }
+}
+
+impl ToRerun<Option<RerunImage>> for Image {
+    fn to_rerun(&self) -> Option<RerunImage> {
+        let converted = ConvertedImage::new(self)?;
+        let resolution = [converted.format.width, converted.format.height];
+
+        if converted.is_depth {
+            let datatype = converted.format.channel_datatype?;
+            Some(RerunImage::DepthImage(
+                rerun::archetypes::DepthImage::from_data_type_and_bytes(
+                    converted.buffer,
+                    resolution,
+                    datatype,
+                ),
+            ))
         } else {
-            None
+            Some(RerunImage::Image(rerun::archetypes::Image::new(
+                converted.buffer,
+                converted.format,
+            )))
         }
     }
 }
 
+/// Only color images can be used as textures: depth images yield `None`.
 impl
     ToRerun<
         Option<(
@@ -138,75 +422,223 @@ impl
         rerun::components::ImageFormat,
         rerun::components::ImageBuffer,
     )> {
-        let color_model = match self.texture_descriptor.format {
-            bevy::render::render_resource::TextureFormat::R8Unorm
-            | bevy::render::render_resource::TextureFormat::R8Snorm
-            | bevy::render::render_resource::TextureFormat::R8Uint
-            | bevy::render::render_resource::TextureFormat::R8Sint => {
-                Some(rerun::datatypes::ColorModel::L)
-            }
+        ConvertedImage::new(self)
+            .filter(|converted| !converted.is_depth)
+            .map(|converted| {
+                (
+                    converted.format.into(),
+                    rerun::components::ImageBuffer(converted.buffer.into()),
+                )
+            })
+    }
+}
 
-            bevy::render::render_resource::TextureFormat::Rgba8Unorm
-            | bevy::render::render_resource::TextureFormat::Rgba8UnormSrgb
-            | bevy::render::render_resource::TextureFormat::Rgba8Snorm
-            | bevy::render::render_resource::TextureFormat::Rgba8Uint
-            | bevy::render::render_resource::TextureFormat::Rgba8Sint => {
-                Some(rerun::datatypes::ColorModel::RGBA)
-            }
+/// The pixels of an [`Image`], in a layout and color space that Rerun understands.
+struct ConvertedImage {
+    format: rerun::datatypes::ImageFormat,
+    buffer: Vec<u8>,
+    is_depth: bool,
+}
 
-            bevy::render::render_resource::TextureFormat::Bgra8Unorm
-            | bevy::render::render_resource::TextureFormat::Bgra8UnormSrgb => {
-                Some(rerun::datatypes::ColorModel::BGRA)
-            }
+impl ConvertedImage {
+    fn new(image: &Image) -> Option<Self> {
+        use rerun::datatypes::{ChannelDatatype as D, ColorModel as C};
+        use TextureFormat as F;
 
-            // TODO(wumpf): many formats aren't covered.
-            _ => None,
+        /// What needs to happen to the raw texture data.
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Layout {
+            /// Nothing, it's already in a format Rerun understands.
+            AsIs,
+
+            /// Two channels, to be expanded to RGB.
+            Rg,
+
+            /// Linear color (RGBA or BGRA), to be encoded to sRGB.
+            Linear,
+
+            Depth,
+        }
+
+        // NOTE: Rerun has no notion of array or 3D images.
+        if image.texture_descriptor.size.depth_or_array_layers != 1 {
+            return None;
+        }
+
+        let (color_model, datatype, layout) = match image.texture_descriptor.format {
+            F::R8Unorm | F::R8Uint => (C::L, D::U8, Layout::AsIs),
+            F::R8Snorm | F::R8Sint => (C::L, D::I8, Layout::AsIs),
+            F::R16Unorm | F::R16Uint => (C::L, D::U16, Layout::AsIs),
+            F::R16Snorm | F::R16Sint => (C::L, D::I16, Layout::AsIs),
+            F::R16Float => (C::L, D::F16, Layout::AsIs),
+            F::R32Uint => (C::L, D::U32, Layout::AsIs),
+            F::R32Sint => (C::L, D::I32, Layout::AsIs),
+            F::R32Float => (C::L, D::F32, Layout::AsIs),
+
+            F::Rg8Unorm | F::Rg8Uint => (C::RGB, D::U8, Layout::Rg),
+            F::Rg8Snorm | F::Rg8Sint => (C::RGB, D::I8, Layout::Rg),
+            F::Rg16Unorm | F::Rg16Uint => (C::RGB, D::U16, Layout::Rg),
+            F::Rg16Snorm | F::Rg16Sint => (C::RGB, D::I16, Layout::Rg),
+            F::Rg16Float => (C::RGB, D::F16, Layout::Rg),
+            F::Rg32Uint => (C::RGB, D::U32, Layout::Rg),
+            F::Rg32Sint => (C::RGB, D::I32, Layout::Rg),
+            F::Rg32Float => (C::RGB, D::F32, Layout::Rg),
+
+            F::Rgba8UnormSrgb | F::Rgba8Uint => (C::RGBA, D::U8, Layout::AsIs),
+            F::Rgba8Unorm => (C::RGBA, D::U8, Layout::Linear),
+            F::Rgba8Snorm | F::Rgba8Sint => (C::RGBA, D::I8, Layout::AsIs),
+            F::Bgra8UnormSrgb => (C::BGRA, D::U8, Layout::AsIs),
+            F::Bgra8Unorm => (C::BGRA, D::U8, Layout::Linear),
+            F::Rgba16Uint => (C::RGBA, D::U16, Layout::AsIs),
+            F::Rgba16Unorm => (C::RGBA, D::U16, Layout::Linear),
+            F::Rgba16Snorm | F::Rgba16Sint => (C::RGBA, D::I16, Layout::AsIs),
+            F::Rgba16Float => (C::RGBA, D::F16, Layout::Linear),
+            F::Rgba32Uint => (C::RGBA, D::U32, Layout::AsIs),
+            F::Rgba32Sint => (C::RGBA, D::I32, Layout::AsIs),
+            F::Rgba32Float => (C::RGBA, D::F32, Layout::Linear),
+
+            F::Depth16Unorm => (C::L, D::U16, Layout::Depth),
+            F::Depth32Float => (C::L, D::F32, Layout::Depth),
+
+            // Compressed and packed formats aren't supported.
+            _ => return None,
+        };
+
+        let buffer = match layout {
+            Layout::AsIs | Layout::Depth => image.data.clone(),
+            Layout::Rg => expand_rg_to_rgb(&image.data, datatype.bits() / 8),
+            Layout::Linear => linear_to_srgb(&image.data, datatype)?,
         };
-        let channel_datatype = match self.texture_descriptor.format {
-            bevy::render::render_resource::TextureFormat::R8Unorm
-            | bevy::render::render_resource::TextureFormat::R8Uint
-            | bevy::render::render_resource::TextureFormat::Rgba8Unorm
-            | bevy::render::render_resource::TextureFormat::Rgba8Uint
-            | bevy::render::render_resource::TextureFormat::Rgba8UnormSrgb
-            | bevy::render::render_resource::TextureFormat::Bgra8Unorm
-            | bevy::render::render_resource::TextureFormat::Bgra8UnormSrgb => {
-                Some(rerun::datatypes::ChannelDatatype::U8)
+
+        Some(Self {
+            format: rerun::datatypes::ImageFormat {
+                width: image.width(),
+                height: image.height(),
+                pixel_format: None,
+                color_model: (layout != Layout::Depth).then_some(color_model),
+                channel_datatype: Some(datatype),
+            },
+            buffer,
+            is_depth: layout == Layout::Depth,
+        })
+    }
+}
+
+/// Appends an empty blue channel to every pixel.
+fn expand_rg_to_rgb(data: &[u8], bytes_per_channel: usize) -> Vec<u8> {
+    let mut rgb = Vec::with_capacity(data.len() / 2 * 3);
+    for rg in data.chunks_exact(bytes_per_channel * 2) {
+        rgb.extend_from_slice(rg);
+        rgb.extend(std::iter::repeat(0).take(bytes_per_channel));
+    }
+    rgb
+}
+
+/// Encodes the color channels of 4-channel linear data to sRGB, leaving alpha untouched.
+///
+/// Works for both RGBA and BGRA.
+fn linear_to_srgb(data: &[u8], datatype: rerun::datatypes::ChannelDatatype) -> Option<Vec<u8>> {
+    use rerun::datatypes::ChannelDatatype as D;
+
+    let encode = Srgba::gamma_function_inverse;
+
+    let mut srgb = data.to_vec();
+    match datatype {
+        D::U8 => {
+            let lut: [u8; 256] =
+                std::array::from_fn(|i| (encode(i as f32 / 255.0) * 255.0).round() as u8);
+            for pixel in srgb.chunks_exact_mut(4) {
+                for c in &mut pixel[..3] {
+                    *c = lut[*c as usize];
+                }
             }
+        }
 
-            bevy::render::render_resource::TextureFormat::R8Snorm
-            | bevy::render::render_resource::TextureFormat::R8Sint
-            | bevy::render::render_resource::TextureFormat::Rgba8Snorm
-            | bevy::render::render_resource::TextureFormat::Rgba8Sint => {
-                Some(rerun::datatypes::ChannelDatatype::I8)
+        D::U16 => {
+            for pixel in srgb.chunks_exact_mut(8) {
+                for c in pixel[..6].chunks_exact_mut(2) {
+                    let value = u16::from_le_bytes([c[0], c[1]]) as f32 / u16::MAX as f32;
+                    let value = (encode(value) * u16::MAX as f32).round() as u16;
+                    c.copy_from_slice(&value.to_le_bytes());
+                }
             }
+        }
 
-            // TODO(wumpf): map more datatypes.
-            _ => None,
-        };
-        // TODO(wumpf): doesn't distinguish between norm/non-norm and srgb/non-srgb.
-
-        let width_height = [self.width(), self.height()];
-
-        color_model.map(|_| {
-            (
-                rerun::datatypes::ImageFormat {
-                    width: width_height[0],
-                    height: width_height[1],
-                    pixel_format: None,
-                    color_model,
-                    channel_datatype,
+        D::F16 => {
+            for pixel in srgb.chunks_exact_mut(8) {
+                for c in pixel[..6].chunks_exact_mut(2) {
+                    let value = half::f16::from_le_bytes([c[0], c[1]]).to_f32();
+                    c.copy_from_slice(&half::f16::from_f32(encode(value)).to_le_bytes());
                 }
-                .into(),
-                rerun::components::ImageBuffer(self.data.clone().into()),
-            )
-        })
+            }
+        }
+
+        D::F32 => {
+            for pixel in srgb.chunks_exact_mut(16) {
+                for c in pixel[..12].chunks_exact_mut(4) {
+                    let value = f32::from_le_bytes([c[0], c[1], c[2], c[3]]);
+                    c.copy_from_slice(&encode(value).to_le_bytes());
+                }
+            }
+        }
+
+        _ => return None,
+    }
+
+    Some(srgb)
+}
+
+/// A camera projection, converted to whichever archetype can represent it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RerunProjection {
+    Perspective(rerun::Pinhole),
+
+    /// Rerun's [`rerun::Pinhole`] cannot represent orthographic projections: the view volume is
+    /// logged as a wireframe box instead, in the camera's frame.
+    Orthographic(rerun::Boxes3D),
+}
+
+impl rerun::AsComponents for RerunProjection {
+    #[inline]
+    fn as_component_batches(&self) -> Vec<rerun::ComponentBatchCowWithDescriptor<'_>> {
+        match self {
+            Self::Perspective(pinhole) => pinhole.as_component_batches(),
+            Self::Orthographic(boxes) => boxes.as_component_batches(),
+        }
     }
 }
 
+/// The view volume, from [`OrthographicProjection::area`] and the near and far planes.
+///
+/// The area is only kept up to date by Bevy's camera systems: see
+/// [`bevy::render::camera::CameraProjection::update`] to compute it from the
+/// [`OrthographicProjection::scaling_mode`] manually.
+impl ToRerun<rerun::Boxes3D> for OrthographicProjection {
+    fn to_rerun(&self) -> rerun::Boxes3D {
+        let OrthographicProjection {
+            near, far, area, ..
+        } = *self;
+
+        // NOTE: Bevy cameras look towards -Z.
+        let center = area.center().extend(-(near + far) / 2.0);
+        let half_size = area.half_size().extend((far - near).abs() / 2.0);
+
+        rerun::Boxes3D::from_centers_and_half_sizes([center.to_rerun()], [half_size.to_rerun()])
+    }
+}
+
+impl ToRerun<RerunProjection> for OrthographicProjection {
+    #[inline]
+    fn to_rerun(&self) -> RerunProjection {
+        RerunProjection::Orthographic(self.to_rerun())
+    }
+}
+
+/// Rerun's [`rerun::Pinhole`] cannot represent orthographic projections: this is only a
+/// placeholder that keeps the camera's axes, see [`RerunProjection`] for the view volume.
 impl ToRerun<rerun::Pinhole> for OrthographicProjection {
     #[inline]
     fn to_rerun(&self) -> rerun::Pinhole {
-        // TODO(cmc): we don't support orthographic cameras, so don't log a frustum for now.
         rerun::Pinhole::new(rerun::Mat3x3::IDENTITY)
             .with_camera_xyz(rerun::components::ViewCoordinates::RUB)
     }
@@ -227,6 +659,13 @@ impl ToRerun<rerun::Pinhole> for PerspectiveProjection {
     }
 }
 
+impl ToRerun<RerunProjection> for PerspectiveProjection {
+    #[inline]
+    fn to_rerun(&self) -> RerunProjection {
+        RerunProjection::Perspective(self.to_rerun())
+    }
+}
+
 impl ToRerun<rerun::Pinhole> for Projection {
     #[inline]
     fn to_rerun(&self) -> rerun::Pinhole {
@@ -236,3 +675,13 @@ impl ToRerun<rerun::Pinhole> for Projection {
         }
     }
 }
+
+impl ToRerun<RerunProjection> for Projection {
+    #[inline]
+    fn to_rerun(&self) -> RerunProjection {
+        match self {
+            Projection::Perspective(p) => p.to_rerun(),
+            Projection::Orthographic(p) => p.to_rerun(),
+        }
+    }
+}
@@ -1,11 +1,17 @@
 use bevy::{
+    asset::UntypedAssetId,
     ecs::component::ComponentInfo,
     prelude::*,
-    render::{mesh::PlaneMeshBuilder, primitives::Aabb},
+    render::{camera::CameraProjection, mesh::PlaneMeshBuilder, primitives::Aabb},
     utils::HashMap,
+    window::PrimaryWindow,
 };
 
-use crate::{compute_entity_path, Aliased, RerunLogger, ToRerun};
+use crate::{
+    assets::{is_instanced, MeshInstanceKey, MeshMaterialId},
+    cached_entity_path, Aliased, RerunLogger, RerunLoggerContext, RerunMesh, RerunProjection,
+    ToRerun,
+};
 
 // ---
 
@@ -34,11 +40,19 @@ impl Default for DefaultRerunComponentLoggers {
 
         loggers.insert(
             "bevy_render::mesh::components::Mesh2d".into(),
-            Some(RerunLogger::new_static(&bevy_mesh2d)),
+            Some(
+                RerunLogger::new_static(&bevy_mesh2d).with_asset_dependencies(|world, entity| {
+                    bevy_mesh_dependencies(world, entity, entity.get::<Mesh2d>().map(|h| h.id()))
+                }),
+            ),
         );
         loggers.insert(
             "bevy_render::mesh::components::Mesh3d".into(),
-            Some(RerunLogger::new_static(&bevy_mesh3d)),
+            Some(
+                RerunLogger::new_static(&bevy_mesh3d).with_asset_dependencies(|world, entity| {
+                    bevy_mesh_dependencies(world, entity, entity.get::<Mesh3d>().map(|h| h.id()))
+                }),
+            ),
         );
 
         loggers.insert(
@@ -56,12 +70,42 @@ impl Default for DefaultRerunComponentLoggers {
 
         loggers.insert(
             "bevy_sprite::sprite::Sprite".into(),
-            Some(RerunLogger::new_static(&bevy_sprite)),
+            Some(
+                RerunLogger::new_static(&bevy_sprite).with_asset_dependencies(|_world, entity| {
+                    entity
+                        .get::<Sprite>()
+                        .map(|sprite| vec![sprite.image.id().untyped()])
+                        .unwrap_or_default()
+                }),
+            ),
         );
 
         loggers.insert(
             "bevy_render::primitives::Aabb".into(),
-            Some(RerunLogger::new_static(&bevy_aabb)),
+            Some(
+                RerunLogger::new_static(&bevy_aabb).with_asset_dependencies(|_world, entity| {
+                    MeshMaterialId::from_entity(entity)
+                        .map(|material| vec![material.untyped()])
+                        .unwrap_or_default()
+                }),
+            ),
+        );
+
+        loggers.insert(
+            "bevy_pbr::light::point_light::PointLight".into(),
+            Some(RerunLogger::new_static(&bevy_point_light)),
+        );
+        loggers.insert(
+            "bevy_pbr::light::spot_light::SpotLight".into(),
+            Some(RerunLogger::new_static(&bevy_spot_light)),
+        );
+        loggers.insert(
+            "bevy_pbr::light::directional_light::DirectionalLight".into(),
+            Some(RerunLogger::new_static(&bevy_directional_light)),
+        );
+        loggers.insert(
+            "bevy_pbr::light::ambient_light::AmbientLight".into(),
+            Some(RerunLogger::typed_resource(bevy_ambient_light)),
         );
 
         loggers.insert(
@@ -74,6 +118,10 @@ impl Default for DefaultRerunComponentLoggers {
         );
 
         loggers.insert("revy::entity_path::RerunEntityPath".into(), None);
+        loggers.insert("revy::sync::CurrentFingerprints".into(), None);
+        loggers.insert("revy::sync::CurrentComponents".into(), None);
+        loggers.insert("revy::sync::UnsyncedEntity".into(), None);
+        loggers.insert("revy::entity_filter::RerunTrack".into(), None);
 
         Self(loggers)
     }
@@ -127,6 +175,12 @@ fn bevy_global_transform<'w>(
     (suffix, data)
 }
 
+/// Triangle meshes are logged once per mesh and material under `assets/meshes`, and instanced at
+/// every entity that uses them: the entity itself only logs the path of its instance.
+///
+/// Other topologies cannot be instanced, and are logged in full.
+///
+/// See `crate::assets`.
 fn bevy_mesh<'w>(
     world: &'w World,
     _all_entities: &'w QueryState<(Entity, Option<&'w Parent>, Option<&'w Name>)>,
@@ -136,36 +190,122 @@ fn bevy_mesh<'w>(
 ) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>) {
     let suffix: Option<&str> = None;
 
-    let data = handle
-        .and_then(|handle| world.resource::<Assets<Mesh>>().get(handle))
-        .and_then(ToRerun::to_rerun)
-        .map(|mut mesh| {
-            if let Some(mat) = entity
-                .get::<MeshMaterial2d<ColorMaterial>>()
-                .and_then(|handle| world.resource::<Assets<ColorMaterial>>().get(handle))
-            {
-                mesh = mesh.with_albedo_factor(mat.color.to_rerun());
+    let Some((handle, mesh)) =
+        handle.and_then(|handle| Some((handle, world.resource::<Assets<Mesh>>().get(handle)?)))
+    else {
+        return (suffix, None);
+    };
+
+    let data = if is_instanced(mesh) {
+        let key = MeshInstanceKey::new(entity, handle.id());
+        Some(Box::new(Aliased::<rerun::components::Text>::new(
+            "revy.components.MeshInstance",
+            key.entity_path().to_string(),
+        )) as _)
+    } else {
+        mesh.to_rerun()
+            .map(|mesh| mesh_with_material(world, mesh, MeshMaterialId::from_entity(entity)))
+            .map(|mesh| Box::new(mesh) as _)
+    };
+
+    (suffix, data)
+}
+
+/// The entity only logs a reference to its instance if `mesh` is instanced, in which case the
+/// contents of its assets don't matter.
+fn bevy_mesh_dependencies(
+    world: &World,
+    entity: EntityRef<'_>,
+    mesh: Option<AssetId<Mesh>>,
+) -> Vec<UntypedAssetId> {
+    let Some(mesh) = mesh else {
+        return Vec::new();
+    };
+    if world
+        .resource::<Assets<Mesh>>()
+        .get(mesh)
+        .is_some_and(is_instanced)
+    {
+        return vec![mesh.untyped()];
+    }
+
+    let material = MeshMaterialId::from_entity(entity);
+    let texture = material.and_then(|material| material.texture(world));
+    std::iter::once(mesh.untyped())
+        .chain(material.map(MeshMaterialId::untyped))
+        .chain(texture.map(AssetId::untyped))
+        .collect()
+}
+
+/// Applies the color and texture of `material`, if any, to `mesh`.
+pub(crate) fn mesh_with_material(
+    world: &World,
+    mesh: RerunMesh,
+    material: Option<MeshMaterialId>,
+) -> RerunMesh {
+    let color = match material {
+        Some(MeshMaterialId::Standard(id)) => world
+            .resource::<Assets<StandardMaterial>>()
+            .get(id)
+            .map(|mat| mat.base_color),
+        Some(MeshMaterialId::Color(id)) => world
+            .resource::<Assets<ColorMaterial>>()
+            .get(id)
+            .map(|mat| mat.color),
+        None => None,
+    };
+    let texture = material.and_then(|material| material.texture(world));
+
+    match mesh {
+        RerunMesh::Mesh3D(mut mesh) => {
+            if let Some(color) = color {
+                mesh = mesh.with_albedo_factor(color.to_rerun());
             }
-            if let Some(mat) = entity
-                .get::<MeshMaterial3d<StandardMaterial>>()
-                .and_then(|handle| world.resource::<Assets<StandardMaterial>>().get(handle))
+            if let Some((image_format, image_data)) =
+                texture.and_then(|id| texture_to_rerun(world, id))
             {
-                mesh = mesh.with_albedo_factor(mat.base_color.to_rerun());
-
-                if let Some((image_format, image_data)) = mat
-                    .base_color_texture
-                    .as_ref()
-                    .and_then(|handle| world.resource::<Assets<Image>>().get(handle))
-                    .and_then(ToRerun::to_rerun)
-                {
-                    mesh = mesh.with_albedo_texture(image_format, image_data);
-                }
+                mesh = mesh.with_albedo_texture(image_format, image_data);
             }
-            mesh
-        })
-        .map(|mesh| Box::new(mesh) as _);
+            RerunMesh::Mesh3D(mesh)
+        }
 
-    (suffix, data)
+        // NOTE: Vertex colors, if any, take precedence over the material's.
+        RerunMesh::LineStrips3D(strips) => match color {
+            Some(color) if strips.colors.is_none() => {
+                RerunMesh::LineStrips3D(strips.with_colors([color.to_rerun()]))
+            }
+            _ => RerunMesh::LineStrips3D(strips),
+        },
+        RerunMesh::Points3D(points) => match color {
+            Some(color) if points.colors.is_none() => {
+                RerunMesh::Points3D(points.with_colors([color.to_rerun()]))
+            }
+            _ => RerunMesh::Points3D(points),
+        },
+    }
+}
+
+/// Converts the [`Image`] identified by `id` into something that can be used as a texture.
+///
+/// With the `compressed_textures` feature, compressed images are decoded through
+/// [`crate::RerunCompressedTextures`].
+pub(crate) fn texture_to_rerun(
+    world: &World,
+    id: AssetId<Image>,
+) -> Option<(
+    rerun::components::ImageFormat,
+    rerun::components::ImageBuffer,
+)> {
+    let image = world.resource::<Assets<Image>>().get(id)?;
+
+    #[cfg(feature = "compressed_textures")]
+    if image.texture_descriptor.format.is_compressed() {
+        return world
+            .get_resource::<crate::RerunCompressedTextures>()?
+            .get_or_decode(id, image);
+    }
+
+    image.to_rerun()
 }
 
 fn bevy_mesh2d<'w>(
@@ -202,21 +342,48 @@ fn bevy_mesh3d<'w>(
     (suffix, data)
 }
 
-fn bevy_camera<'w, C: Component + ToRerun<rerun::Pinhole>>(
-    _world: &'w World,
+/// The projection is evaluated against the size of the camera's viewport, or the primary
+/// window's if it isn't known yet, as Bevy only does so once it's been rendered.
+fn bevy_camera<'w, C>(
+    world: &'w World,
     _all_entities: &'w QueryState<(Entity, Option<&'w Parent>, Option<&'w Name>)>,
     entity: EntityRef<'_>,
     _component: &'w ComponentInfo,
-) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>) {
+) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>)
+where
+    C: Component + Clone + CameraProjection + ToRerun<RerunProjection>,
+{
     let suffix = Some("cam");
     let data = entity
         .get::<C>()
+        .map(|projection| {
+            let mut projection = projection.clone();
+            let viewport_size = entity
+                .get::<Camera>()
+                .and_then(Camera::logical_viewport_size)
+                .or_else(|| primary_window_size(world));
+            if let Some(size) = viewport_size {
+                projection.update(size.x, size.y);
+            }
+            projection
+        })
         // TODO(cmc): log visible entities too?
-        .map(ToRerun::to_rerun)
-        .map(|mesh| Box::new(mesh) as _);
+        .map(|projection| projection.to_rerun())
+        .map(|projection| Box::new(projection) as _);
     (suffix, data)
 }
 
+fn primary_window_size(world: &World) -> Option<Vec2> {
+    let primary_window = world.component_id::<PrimaryWindow>()?;
+    world
+        .archetypes()
+        .iter()
+        .filter(|archetype| archetype.contains(primary_window))
+        .flat_map(|archetype| archetype.entities())
+        .find_map(|entity| world.get::<Window>(entity.id()))
+        .map(Window::size)
+}
+
 fn bevy_projection<'w>(
     world: &'w World,
     all_entities: &'w QueryState<(Entity, Option<&'w Parent>, Option<&'w Name>)>,
@@ -256,20 +423,19 @@ fn bevy_sprite<'w>(
     let data = entity
         .get::<Sprite>()
         .and_then(|sprite| {
-            world
-                .resource::<Assets<Image>>()
-                .get(sprite.image.id())
-                .and_then(ToRerun::to_rerun)
-                .and_then(|(image_format, image_data)| {
-                    let mesh = PlaneMeshBuilder::default()
-                        .normal(Dir3::Z)
-                        .size(image_format.width as _, image_format.height as _)
-                        .build();
-                    mesh.to_rerun().map(|mesh| {
-                        mesh.with_albedo_factor(sprite.color.to_rerun())
-                            .with_albedo_texture(image_format, image_data)
-                    })
-                })
+            texture_to_rerun(world, sprite.image.id()).and_then(|(image_format, image_data)| {
+                let mesh = PlaneMeshBuilder::default()
+                    .normal(Dir3::Z)
+                    .size(image_format.width as _, image_format.height as _)
+                    .build();
+                let Some(RerunMesh::Mesh3D(mesh)) = mesh.to_rerun() else {
+                    return None;
+                };
+                Some(
+                    mesh.with_albedo_factor(sprite.color.to_rerun())
+                        .with_albedo_texture(image_format, image_data),
+                )
+            })
         })
         .map(|data| Box::new(data) as _);
 
@@ -313,6 +479,123 @@ fn bevy_aabb<'w>(
     (suffix, data)
 }
 
+// NOTE: Lights are drawn in their local space, the entity's transform takes care of the rest.
+// Bevy lights shine towards their local -Z axis.
+//
+// The points and their range spheres deliberately leave their radii unset: both would end up in the
+// same `Radius` component otherwise.
+
+fn bevy_point_light<'w>(
+    _world: &'w World,
+    _all_entities: &'w QueryState<(Entity, Option<&'w Parent>, Option<&'w Name>)>,
+    entity: EntityRef<'_>,
+    _component: &'w ComponentInfo,
+) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>) {
+    let suffix = Some("light");
+
+    let data = entity.get::<PointLight>().map(|light| {
+        Box::new(
+            rerun::Points3D::new([Vec3::ZERO.to_rerun()]).with_colors([light.color.to_rerun()]),
+        ) as _
+    });
+
+    (suffix, data)
+}
+
+/// The range of a [`PointLight`], logged at `comps/light_range` on top of the light itself.
+///
+/// NOTE: This can't share the light's suffix, since both archetypes have colors and radii.
+pub(crate) fn bevy_point_light_range(
+    light: &PointLight,
+    _ctx: &RerunLoggerContext<'_>,
+) -> Option<rerun::Ellipsoids3D> {
+    Some(
+        rerun::Ellipsoids3D::from_radii([light.range])
+            .with_colors([light.color.to_rerun()])
+            .with_fill_mode(rerun::FillMode::MajorWireframe),
+    )
+}
+
+fn bevy_ambient_light(
+    light: &AmbientLight,
+    _world: &World,
+) -> Option<Vec<Box<dyn rerun::AsComponents>>> {
+    Some(vec![
+        Box::new(rerun::Scalar::new(f64::from(light.brightness))),
+        Box::new(
+            rerun::SeriesLine::new()
+                .with_color(light.color.to_rerun())
+                .with_name("brightness"),
+        ),
+    ])
+}
+
+fn bevy_spot_light<'w>(
+    _world: &'w World,
+    _all_entities: &'w QueryState<(Entity, Option<&'w Parent>, Option<&'w Name>)>,
+    entity: EntityRef<'_>,
+    _component: &'w ComponentInfo,
+) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>) {
+    let suffix = Some("light");
+
+    let data = entity.get::<SpotLight>().map(|light| {
+        const SEGMENTS: usize = 32;
+
+        // The base of the cone, on the sphere of radius `range` around the light.
+        //
+        // NOTE: Bevy clamps the angles to `[0, π/2]` too, and using sin/cos rather than tan keeps
+        // the cone bounded all the way up to a half-sphere.
+        let circle = |angle: f32| {
+            let angle = angle.clamp(0.0, std::f32::consts::FRAC_PI_2);
+            let (radius, depth) = (light.range * angle.sin(), light.range * angle.cos());
+            (0..=SEGMENTS)
+                .map(|i| {
+                    let theta = i as f32 / SEGMENTS as f32 * std::f32::consts::TAU;
+                    Vec3::new(radius * theta.cos(), radius * theta.sin(), -depth)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let outer = circle(light.outer_angle);
+        let inner = circle(light.inner_angle);
+
+        let mut strips = (0..4)
+            .map(|i| vec![Vec3::ZERO, outer[i * SEGMENTS / 4]])
+            .collect::<Vec<_>>();
+        strips.push(outer);
+        strips.push(inner);
+
+        let strips = strips.iter().map(|strip| {
+            strip
+                .iter()
+                .map(ToRerun::to_rerun)
+                .collect::<Vec<rerun::Vec3D>>()
+        });
+
+        Box::new(rerun::LineStrips3D::new(strips).with_colors([light.color.to_rerun()])) as _
+    });
+
+    (suffix, data)
+}
+
+fn bevy_directional_light<'w>(
+    _world: &'w World,
+    _all_entities: &'w QueryState<(Entity, Option<&'w Parent>, Option<&'w Name>)>,
+    entity: EntityRef<'_>,
+    _component: &'w ComponentInfo,
+) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>) {
+    let suffix = Some("light");
+
+    let data = entity.get::<DirectionalLight>().map(|light| {
+        Box::new(
+            rerun::Arrows3D::from_vectors([Vec3::NEG_Z.to_rerun()])
+                .with_colors([light.color.to_rerun()]),
+        ) as _
+    });
+
+    (suffix, data)
+}
+
 fn bevy_parent<'w>(
     world: &'w World,
     all_entities: &'w QueryState<(Entity, Option<&'w Parent>, Option<&'w Name>)>,
@@ -323,7 +606,7 @@ fn bevy_parent<'w>(
     let data = entity
         .get::<Parent>()
         .map(|parent| {
-            let parent_entity_path = compute_entity_path(world, all_entities, parent.get());
+            let parent_entity_path = cached_entity_path(world, all_entities, parent.get());
             Aliased::<rerun::datatypes::EntityPath>::new(
                 "Parent",
                 rerun::datatypes::EntityPath(parent_entity_path.to_string().into()),
@@ -350,7 +633,7 @@ fn bevy_children<'w>(
     //             .iter()
     //             .map(|entity_id| {
     //                 rerun::datatypes::EntityPath(
-    //                     compute_entity_path(world, all_entities, *entity_id)
+    //                     cached_entity_path(world, all_entities, *entity_id)
     //                         .to_string()
     //                         .into(),
     //                 )
@@ -368,7 +651,7 @@ fn bevy_children<'w>(
         .map(|children| {
             let children = children
                 .iter()
-                .map(|entity_id| compute_entity_path(world, all_entities, *entity_id).to_string())
+                .map(|entity_id| cached_entity_path(world, all_entities, *entity_id).to_string())
                 .collect::<Vec<_>>();
             Aliased::<rerun::components::Text>::new(
                 "RawChildren",
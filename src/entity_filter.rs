@@ -0,0 +1,73 @@
+use bevy::{
+    ecs::{entity::EntityHashSet, query::QueryFilter},
+    prelude::*,
+};
+
+// ---
+
+/// Marks an entity, and all of its descendants, as not to be synced.
+///
+/// Takes precedence over [`RerunTrack`].
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct RerunIgnore;
+
+/// Marks an entity, and all of its descendants, as to be synced.
+///
+/// Only has an effect in [`RerunTrackingMode::OptIn`] mode.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct RerunTrack;
+
+/// Which entities get synced with the recording.
+///
+/// Either way, only entities that match the `QueryFilter` of the [`crate::RerunPlugin`] are
+/// considered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RerunTrackingMode {
+    /// Every entity gets synced, except those marked with [`RerunIgnore`] and their descendants.
+    #[default]
+    OptOut,
+
+    /// Only entities marked with [`RerunTrack`] and their descendants get synced.
+    ///
+    /// [`RerunIgnore`] still applies within tracked subtrees.
+    OptIn,
+}
+
+/// Returns all the entities that should be synced, according to `F` and `mode`.
+pub(crate) fn synced_entities<F: QueryFilter>(
+    world: &mut World,
+    mode: RerunTrackingMode,
+) -> Vec<Entity> {
+    let _trace = info_span!("synced_entities").entered();
+
+    let ignored = marked_subtrees::<RerunIgnore>(world);
+    let tracked = (mode == RerunTrackingMode::OptIn).then(|| marked_subtrees::<RerunTrack>(world));
+
+    let mut entities = world.query_filtered::<Entity, F>();
+    entities
+        .iter(world)
+        .filter(|entity_id| !ignored.contains(entity_id))
+        .filter(|entity_id| {
+            tracked
+                .as_ref()
+                .map_or(true, |tracked| tracked.contains(entity_id))
+        })
+        .collect()
+}
+
+/// Returns all entities marked with `M`, as well as all of their descendants.
+fn marked_subtrees<M: Component>(world: &mut World) -> EntityHashSet {
+    let mut marked = world.query_filtered::<Entity, With<M>>();
+    let mut stack = marked.iter(world).collect::<Vec<_>>();
+
+    let mut entities = EntityHashSet::default();
+    while let Some(entity_id) = stack.pop() {
+        if entities.insert(entity_id) {
+            if let Some(children) = world.get::<Children>(entity_id) {
+                stack.extend(children.iter().copied());
+            }
+        }
+    }
+
+    entities
+}
@@ -2,6 +2,28 @@ use bevy::prelude::*;
 
 // ---
 
+/// The cached [`rerun::EntityPath`] of an entity.
+///
+/// Revy keeps it up to date as the hierarchy changes, you shouldn't ever need to insert or modify
+/// it yourself.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Deref)]
+pub struct RerunEntityPath(pub(crate) rerun::EntityPath);
+
+/// Returns the cached [`RerunEntityPath`] of `entity_id` if it has one, or computes it from
+/// scratch otherwise.
+///
+/// See [`compute_entity_path`].
+pub fn cached_entity_path<'w: 's, 's>(
+    world: &'w World,
+    entities: &'w QueryState<(Entity, Option<&'s Parent>, Option<&'s Name>)>,
+    entity_id: Entity,
+) -> rerun::EntityPath {
+    world.get::<RerunEntityPath>(entity_id).map_or_else(
+        || compute_entity_path(world, entities, entity_id),
+        |entity_path| entity_path.0.clone(),
+    )
+}
+
 /// Iterates over the ancestors of `entity_id`, in ascending order (parent, grand-parent, grand-grand-parent, …).
 ///
 /// `entities` must have been updated manually before calling this function, or the results will be
@@ -0,0 +1,80 @@
+use bevy::{ecs::event::EventCursor, prelude::*};
+use rerun::external::re_log::ResultExt;
+
+use crate::{
+    rerun_logger::reflect_to_ron,
+    sync::{set_recording_time, RerunSyncState},
+};
+
+// ---
+
+/// The callback type to convert a Bevy event into Rerun component data.
+///
+/// Returning `None` skips the event entirely.
+pub trait RerunEventLoggerFn<T>:
+    Send + Sync + for<'w> Fn(&'w World, &'w T) -> Option<Box<dyn rerun::AsComponents>>
+{
+}
+
+impl<T, F> RerunEventLoggerFn<T> for F where
+    F: Send + Sync + for<'w> Fn(&'w World, &'w T) -> Option<Box<dyn rerun::AsComponents>>
+{
+}
+
+/// Logs an event as a [`rerun::TextLog`], serialized to RON via reflection.
+pub(crate) fn log_reflected_event<T: Event + Reflect>(
+    world: &World,
+    event: &T,
+) -> Option<Box<dyn rerun::AsComponents>> {
+    let type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = type_registry.read();
+
+    let body = reflect_to_ron(event.as_partial_reflect(), &type_registry)
+        .unwrap_or_else(|| "<missing reflection metadata>".into());
+
+    Some(Box::new(rerun::TextLog::new(body)) as _)
+}
+
+/// Returns a system that logs every `T` event at `events/<type_name>`, using `logger`.
+///
+/// The system owns its own [`EventCursor`]: it sees every event that was sent since it last ran,
+/// regardless of whether other systems already read them in the meantime.
+/// Events only get dropped once they've lived through two frame updates, so even events that are
+/// sent and consumed within a single frame get logged.
+pub(crate) fn system_log_events<T: Event>(
+    logger: impl RerunEventLoggerFn<T> + 'static,
+) -> impl FnMut(&World, Local<'_, EventCursor<T>>) {
+    let entity_path = rerun::EntityPath::new(vec![
+        rerun::EntityPathPart::new("events"),
+        rerun::EntityPathPart::new(std::any::type_name::<T>()),
+    ]);
+
+    move |world, mut cursor| {
+        let _trace = info_span!("log_events", event = std::any::type_name::<T>()).entered();
+
+        let Some(state) = world.get_resource::<RerunSyncState>() else {
+            return;
+        };
+        let Some(events) = world.get_resource::<Events<T>>() else {
+            return;
+        };
+
+        let rec = &state.rec;
+        set_recording_time(world, rec);
+
+        for event in cursor.read(events) {
+            let Some(data) = logger(world, event) else {
+                continue;
+            };
+
+            rec.log_component_batches(
+                entity_path.clone(),
+                false,
+                data.as_component_batches()
+                    .iter()
+                    .map(|batch| batch as &dyn rerun::ComponentBatch),
+            )
+            .ok_or_log_error();
+        }
+    }
+}
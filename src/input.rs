@@ -0,0 +1,317 @@
+use std::{collections::BTreeMap, path::PathBuf, time::Duration};
+
+use bevy::{
+    input::{
+        gamepad::{
+            gamepad_connection_system, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
+            RawGamepadButtonChangedEvent, RawGamepadEvent,
+        },
+        keyboard::{keyboard_input_system, KeyboardInput},
+        mouse::{
+            accumulate_mouse_motion_system, accumulate_mouse_scroll_system,
+            mouse_button_input_system, MouseButtonInput, MouseMotion, MouseWheel,
+        },
+        InputSystem,
+    },
+    prelude::*,
+    reflect::{serde::ReflectDeserializer, FromReflect, GetTypeRegistration, TypeRegistry},
+    time::{TimeSystem, TimeUpdateStrategy},
+    utils::HashMap,
+    window::CursorMoved,
+};
+use rerun::{external::re_log::ResultExt, log::RowId};
+
+use crate::{
+    replay::RerunRecording,
+    sync::{set_recording_time, RerunLogSet, RerunSyncState},
+    Aliased, RerunAppExt as _,
+};
+
+// ---
+
+/// Registers `$f::<T>` for every raw input event type that gets captured and replayed.
+macro_rules! for_each_input_event {
+    ($f:ident($($arg:expr),*)) => {{
+        $f::<KeyboardInput>($($arg),*);
+        $f::<MouseButtonInput>($($arg),*);
+        $f::<MouseMotion>($($arg),*);
+        $f::<MouseWheel>($($arg),*);
+        $f::<CursorMoved>($($arg),*);
+        $f::<RawGamepadEvent>($($arg),*);
+        $f::<GamepadConnectionEvent>($($arg),*);
+        $f::<RawGamepadButtonChangedEvent>($($arg),*);
+        $f::<RawGamepadAxisChangedEvent>($($arg),*);
+    }};
+}
+
+// --- Capture ---
+
+/// Logs all raw input events at `events/<type_name>`, as well as the state of the keyboard and
+/// mouse buttons and the frame deltas at `inputs/…`, so that [`RerunInputReplayPlugin`] can later
+/// play them back.
+pub(crate) struct RerunInputCapturePlugin;
+
+impl Plugin for RerunInputCapturePlugin {
+    fn build(&self, app: &mut App) {
+        fn capture<T: Event + Reflect + GetTypeRegistration>(app: &mut App) {
+            app.rerun_log_events::<T>();
+        }
+        for_each_input_event!(capture(app));
+
+        app.add_systems(Last, system_log_inputs.in_set(RerunLogSet));
+    }
+}
+
+fn system_log_inputs(
+    world: &World,
+    keys: Option<Res<'_, ButtonInput<KeyCode>>>,
+    mouse_buttons: Option<Res<'_, ButtonInput<MouseButton>>>,
+    time: Option<Res<'_, Time<Real>>>,
+) {
+    let _trace = info_span!("log_inputs").entered();
+
+    let Some(state) = world.get_resource::<RerunSyncState>() else {
+        return;
+    };
+
+    let rec = &state.rec;
+    set_recording_time(world, rec);
+
+    fn pressed<T: std::fmt::Debug + Copy + Eq + std::hash::Hash + Send + Sync>(
+        input: &ButtonInput<T>,
+    ) -> Aliased<rerun::components::Text> {
+        let pressed = input.get_pressed().map(|input| format!("{input:?}"));
+        Aliased::new("Pressed", pressed.collect::<Vec<_>>().join("\n"))
+    }
+
+    if let Some(keys) = keys.filter(|keys| keys.is_changed()) {
+        rec.log("inputs/keyboard", &pressed(&keys))
+            .ok_or_log_error();
+    }
+
+    if let Some(mouse_buttons) = mouse_buttons.filter(|buttons| buttons.is_changed()) {
+        rec.log("inputs/mouse", &pressed(&mouse_buttons))
+            .ok_or_log_error();
+    }
+
+    if let Some(time) = time {
+        rec.log(
+            "inputs/delta",
+            &rerun::Scalar::new(time.delta().as_secs_f64()),
+        )
+        .ok_or_log_error();
+    }
+}
+
+// --- Replay ---
+
+/// Feeds the inputs captured by [`crate::RerunPlugin::with_input_capture`] back into the app,
+/// frame by frame, instead of the real input devices.
+///
+/// The frame deltas are replayed too, using [`TimeUpdateStrategy::ManualDuration`]. The previous
+/// [`TimeUpdateStrategy`] is restored once the end of the recording is reached.
+/// Combined with seeded RNGs, this makes for deterministic reproductions of whatever happened in
+/// the recording.
+pub struct RerunInputReplayPlugin {
+    /// The `.rrd` file to replay the inputs from.
+    pub path: PathBuf,
+}
+
+impl RerunInputReplayPlugin {
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Plugin for RerunInputReplayPlugin {
+    fn build(&self, app: &mut App) {
+        let recording = match RerunRecording::from_rrd_filepath(&self.path) {
+            Ok(recording) => recording,
+            Err(err) => {
+                error!(path = ?self.path, %err, "failed to load recording, input replay disabled");
+                return;
+            }
+        };
+
+        let mut senders: HashMap<String, EventSender> = HashMap::default();
+        fn register<T: Event + FromReflect>(senders: &mut HashMap<String, EventSender>) {
+            senders.insert(std::any::type_name::<T>().to_owned(), send_reflected::<T>);
+        }
+        for_each_input_event!(register(&mut senders));
+
+        let frames = recorded_inputs(&recording, &senders);
+
+        app.insert_resource(RerunInputReplay {
+            next_frame: frames.keys().next().copied().unwrap_or_default(),
+            frames,
+            senders,
+            previous_strategy: None,
+        })
+        .add_systems(First, system_replay_delta.before(TimeSystem))
+        // NOTE: Gamepad events are produced by gilrs right before `InputSystem`: the real events
+        // must be dropped after that, but before any of the input systems get to process them.
+        .add_systems(
+            PreUpdate,
+            system_replay_events
+                .in_set(InputSystem)
+                .before(keyboard_input_system)
+                .before(mouse_button_input_system)
+                .before(accumulate_mouse_motion_system)
+                .before(accumulate_mouse_scroll_system)
+                .before(gamepad_connection_system),
+        )
+        .add_systems(Last, system_next_frame);
+    }
+}
+
+type EventSender = fn(&mut World, &dyn PartialReflect);
+
+fn send_reflected<T: Event + FromReflect>(world: &mut World, reflected: &dyn PartialReflect) {
+    if let Some(event) = T::from_reflect(reflected) {
+        world.send_event(event);
+    }
+}
+
+/// The inputs of a single frame.
+#[derive(Default)]
+struct RecordedInputs {
+    /// In the order they were logged in.
+    events: Vec<(RowId, String, String)>,
+    delta: Option<Duration>,
+}
+
+#[derive(Resource)]
+struct RerunInputReplay {
+    frames: BTreeMap<i64, RecordedInputs>,
+    senders: HashMap<String, EventSender>,
+    next_frame: i64,
+
+    /// The [`TimeUpdateStrategy`] that was in use before the replay started overriding it.
+    previous_strategy: Option<TimeUpdateStrategy>,
+}
+
+impl RerunInputReplay {
+    /// Whether all the recorded frames have been replayed.
+    fn is_finished(&self) -> bool {
+        self.frames
+            .last_key_value()
+            .map_or(true, |(last_frame, _)| self.next_frame > *last_frame)
+    }
+}
+
+/// Extracts all the recorded inputs, indexed by frame.
+fn recorded_inputs(
+    recording: &RerunRecording,
+    senders: &HashMap<String, EventSender>,
+) -> BTreeMap<i64, RecordedInputs> {
+    let timeline = rerun::Timeline::new_sequence("frame");
+    let text_name = <rerun::components::Text as rerun::Component>::name();
+    let scalar_name = <rerun::components::Scalar as rerun::Component>::name();
+
+    let delta_path = rerun::EntityPath::from("inputs/delta");
+    let event_paths = senders
+        .keys()
+        .map(|type_name| {
+            let entity_path = rerun::EntityPath::new(vec![
+                rerun::EntityPathPart::new("events"),
+                rerun::EntityPathPart::new(type_name.as_str()),
+            ]);
+            (entity_path, type_name)
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut frames: BTreeMap<i64, RecordedInputs> = BTreeMap::new();
+    for chunk in recording.chunks() {
+        if chunk.entity_path() == &delta_path {
+            let deltas = chunk
+                .iter_component_indices(&timeline, &scalar_name)
+                .zip(chunk.iter_component::<rerun::components::Scalar>());
+            for ((frame, _), delta) in deltas {
+                if let Some(delta) = delta.first() {
+                    frames.entry(frame.as_i64()).or_default().delta =
+                        Some(Duration::from_secs_f64(delta.0 .0));
+                }
+            }
+        } else if let Some(type_name) = event_paths.get(chunk.entity_path()) {
+            let events = chunk
+                .iter_component_indices(&timeline, &text_name)
+                .zip(chunk.iter_string(&text_name));
+            for ((frame, row_id), bodies) in events {
+                let Some(body) = bodies.into_iter().next() else {
+                    continue;
+                };
+                frames.entry(frame.as_i64()).or_default().events.push((
+                    row_id,
+                    (*type_name).clone(),
+                    body.to_string(),
+                ));
+            }
+        }
+    }
+
+    for inputs in frames.values_mut() {
+        inputs.events.sort_by_key(|(row_id, _, _)| *row_id);
+    }
+
+    frames
+}
+
+fn system_replay_delta(
+    mut replay: ResMut<'_, RerunInputReplay>,
+    mut strategy: ResMut<'_, TimeUpdateStrategy>,
+) {
+    let replay = &mut *replay;
+
+    if let Some(delta) = replay
+        .frames
+        .get(&replay.next_frame)
+        .and_then(|inputs| inputs.delta)
+    {
+        let previous = std::mem::replace(&mut *strategy, TimeUpdateStrategy::ManualDuration(delta));
+        replay.previous_strategy.get_or_insert(previous);
+    } else if replay.is_finished() {
+        if let Some(previous) = replay.previous_strategy.take() {
+            *strategy = previous;
+        }
+    }
+}
+
+fn system_replay_events(world: &mut World) {
+    world.resource_scope(|world, replay: Mut<'_, RerunInputReplay>| {
+        // Drop whatever the real input devices had to say.
+        fn clear<T: Event>(world: &mut World) {
+            if let Some(mut events) = world.get_resource_mut::<Events<T>>() {
+                events.clear();
+            }
+        }
+        for_each_input_event!(clear(world));
+
+        let Some(inputs) = replay.frames.get(&replay.next_frame) else {
+            return;
+        };
+
+        let type_registry = world.resource::<AppTypeRegistry>().clone();
+        let type_registry = type_registry.read();
+
+        for (_, type_name, body) in &inputs.events {
+            let Some(reflected) = deserialize(&type_registry, body) else {
+                warn_once!("failed to deserialize recorded input event: {type_name}");
+                continue;
+            };
+            if let Some(send) = replay.senders.get(type_name) {
+                send(world, &*reflected);
+            }
+        }
+    });
+}
+
+fn system_next_frame(mut replay: ResMut<'_, RerunInputReplay>) {
+    replay.next_frame += 1;
+}
+
+fn deserialize(type_registry: &TypeRegistry, body: &str) -> Option<Box<dyn PartialReflect>> {
+    ron::Options::default()
+        .from_str_seed(body, ReflectDeserializer::new(type_registry))
+        .ok()
+}
@@ -2,41 +2,246 @@
 #![allow(clippy::doc_markdown)]
 #![doc = include_str!("../README.md")]
 
-use bevy::prelude::*;
+use std::marker::PhantomData;
 
-// ---
+use bevy::{ecs::query::QueryFilter, prelude::*};
 
-// TODO(cmc): support for bug report mode (buffering + kickoff)
+// ---
 
-pub struct RerunPlugin {
+/// Syncs the Bevy world with a Rerun recording.
+///
+/// Only the entities matching the `F` query filter are synced, see also [`RerunTrackingMode`].
+///
+/// Use [`RerunPlugin::new`] (or `rec.into()`) and the `with_*` methods to build one.
+/// Struct literals must fill in the rest of the fields from [`RerunPlugin::new`], e.g.
+/// `RerunPlugin { sync_resources: true, ..RerunPlugin::new(rec) }`.
+pub struct RerunPlugin<F = ()> {
     pub rec: RecordingStream,
+
+    /// If set, the recording is kept in a bounded in-memory buffer and only written to disk on
+    /// demand.
+    ///
+    /// See [`BugReportSettings`] for more information.
+    pub bug_report: Option<BugReportSettings>,
+
+    /// Which entities get synced, on top of `F`.
+    pub tracking_mode: RerunTrackingMode,
+
+    /// Whether resources get synced too, under `resources/<type_name>`. Disabled by default.
+    ///
+    /// See [`RerunLogger::with_resource`] for more information.
+    pub sync_resources: bool,
+
+    /// Which timelines the recording is indexed by.
+    ///
+    /// See [`RerunTimelines`] for more information.
+    pub timelines: RerunTimelines,
+
+    /// Additional points in the frame at which the world gets synced, on top of `Last`.
+    ///
+    /// See [`RerunSyncPoint`] for more information.
+    pub sync_points: Vec<RerunSyncPoint>,
+
+    /// Whether raw inputs and frame deltas get captured, so that they can be replayed later on.
+    ///
+    /// See [`RerunInputReplayPlugin`] for more information.
+    pub capture_inputs: bool,
+
+    /// Set through [`RerunPlugin::with_filter`].
+    pub filter: PhantomData<fn() -> F>,
+}
+
+impl From<RecordingStream> for RerunPlugin {
+    #[inline]
+    fn from(rec: RecordingStream) -> Self {
+        Self::new(rec)
+    }
+}
+
+impl RerunPlugin {
+    #[inline]
+    pub fn new(rec: RecordingStream) -> Self {
+        Self {
+            rec,
+            bug_report: None,
+            tracking_mode: RerunTrackingMode::default(),
+            sync_resources: false,
+            timelines: RerunTimelines::default(),
+            sync_points: Vec::new(),
+            capture_inputs: false,
+            filter: PhantomData,
+        }
+    }
+}
+
+impl<F> RerunPlugin<F> {
+    /// Runs in bug report mode.
+    ///
+    /// See [`BugReportSettings`] for more information.
+    #[inline]
+    pub fn with_bug_report(mut self, settings: BugReportSettings) -> Self {
+        self.bug_report = Some(settings);
+        self
+    }
+
+    /// See [`RerunTrackingMode`].
+    #[inline]
+    pub fn with_tracking_mode(mut self, tracking_mode: RerunTrackingMode) -> Self {
+        self.tracking_mode = tracking_mode;
+        self
+    }
+
+    /// Whether resources get synced too, under `resources/<type_name>`. Disabled by default.
+    #[inline]
+    pub fn with_resources(mut self, sync_resources: bool) -> Self {
+        self.sync_resources = sync_resources;
+        self
+    }
+
+    /// See [`RerunTimelines`].
+    #[inline]
+    pub fn with_timelines(mut self, timelines: RerunTimelines) -> Self {
+        self.timelines = timelines;
+        self
+    }
+
+    /// Also sync the world at `sync_point`.
+    ///
+    /// E.g. `RerunSyncPoint::new(FixedPostUpdate, "fixed_sync")` to be able to inspect the state
+    /// of the world in between each `FixedUpdate` iteration.
+    #[inline]
+    pub fn with_sync_point(mut self, sync_point: RerunSyncPoint) -> Self {
+        self.sync_points.push(sync_point);
+        self
+    }
+
+    /// Captures raw inputs and frame deltas, so that they can be replayed later on.
+    ///
+    /// This implies the `frame` timeline, see [`RerunInputReplayPlugin`].
+    #[inline]
+    pub fn with_input_capture(mut self) -> Self {
+        self.capture_inputs = true;
+        self
+    }
+
+    /// Only sync the entities that match the `G` query filter.
+    ///
+    /// E.g. `RerunPlugin::new(rec).with_filter::<Without<Node>>()` to ignore all UI nodes.
+    #[inline]
+    pub fn with_filter<G: QueryFilter>(self) -> RerunPlugin<G> {
+        let Self {
+            rec,
+            bug_report,
+            tracking_mode,
+            sync_resources,
+            timelines,
+            sync_points,
+            capture_inputs,
+            filter: _,
+        } = self;
+        RerunPlugin {
+            rec,
+            bug_report,
+            tracking_mode,
+            sync_resources,
+            timelines,
+            sync_points,
+            capture_inputs,
+            filter: PhantomData,
+        }
+    }
 }
 
-impl Plugin for RerunPlugin {
+impl<F: QueryFilter + 'static> Plugin for RerunPlugin<F> {
     fn build(&self, app: &mut App) {
-        app.add_plugins(RerunSyncPlugin {
+        // NOTE: The bug report plugin swaps the sink, so it must come first in order not to miss
+        // any data.
+        if let Some(settings) = self.bug_report.clone() {
+            app.add_plugins(RerunBugReportPlugin {
+                rec: self.rec.clone(),
+                settings,
+            });
+        }
+
+        // NOTE: Captured inputs are replayed frame by frame.
+        let timelines = RerunTimelines {
+            frame: self.timelines.frame || self.capture_inputs,
+            ..self.timelines
+        };
+
+        app.add_plugins(RerunSyncPlugin::<F> {
             rec: self.rec.clone(),
+            tracking_mode: self.tracking_mode,
+            sync_resources: self.sync_resources,
+            timelines,
+            sync_points: self.sync_points.clone(),
+            filter: PhantomData,
         });
+
+        if self.capture_inputs {
+            app.add_plugins(RerunInputCapturePlugin);
+        }
     }
 }
 
 // ---
 
+mod app_ext;
+mod assets;
+mod bug_report;
+mod change_dedup;
+#[cfg(feature = "compressed_textures")]
+mod compressed_textures;
 mod conversions;
 mod default_loggers;
+mod entity_filter;
 mod entity_path;
+mod events;
+mod input;
+mod replay;
 mod rerun_logger;
+mod states;
 mod sync;
+mod timelines;
+
+#[cfg(feature = "testing")]
+pub mod testing;
 
-pub use self::conversions::ToRerun;
+pub use self::app_ext::RerunAppExt;
+pub use self::bug_report::{BugReportSettings, BugReportWindow, RerunBugReport};
+pub use self::change_dedup::{
+    AutoDedup, BytesDedup, ChangeDedup, ChangeFingerprint, NoDedup, ReflectHashDedup,
+    ReflectPartialEqDedup, RerunChangeDedup,
+};
+#[cfg(feature = "compressed_textures")]
+pub use self::compressed_textures::{decompress, RerunCompressedTextures};
+pub use self::conversions::{RerunImage, RerunMesh, RerunProjection, ToRerun};
 pub use self::default_loggers::DefaultRerunComponentLoggers;
-pub use self::entity_path::{ancestors_from_world, compute_entity_path};
+pub use self::entity_filter::{RerunIgnore, RerunTrack, RerunTrackingMode};
+pub use self::entity_path::{
+    ancestors_from_world, cached_entity_path, compute_entity_path, RerunEntityPath,
+};
+pub use self::events::RerunEventLoggerFn;
+pub use self::input::RerunInputReplayPlugin;
+pub use self::replay::{RerunReplayPlugin, RerunReplayRequest, RerunReplayTime};
+#[allow(deprecated)]
+pub use self::rerun_logger::get_component_logger;
 pub use self::rerun_logger::{
-    get_component_logger, Aliased, RerunComponentLoggers, RerunLogger, RerunLoggerFn,
+    get_component_loggers, get_resource_logger, Aliased, ReflectRerunLogger,
+    RerunAssetDependenciesFn, RerunComponentLoggers, RerunLog, RerunLogger, RerunLoggerChain,
+    RerunLoggerContext, RerunLoggerFn, RerunReflectedComponentLoggers, RerunResourceLoggerFn,
+    RerunTypedComponentLoggers,
 };
 
+pub(crate) use self::bug_report::RerunBugReportPlugin;
+pub(crate) use self::input::RerunInputCapturePlugin;
+pub use self::sync::{RerunSyncPoint, RerunSyncSet};
+pub use self::timelines::RerunTimelines;
+
 pub(crate) use self::sync::RerunSyncPlugin;
 
+pub use revy_derive::RerunLog;
+
 pub use rerun::{RecordingStream, RecordingStreamBuilder}; // convenience
 
 pub mod external {
@@ -0,0 +1,481 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{
+    prelude::*,
+    reflect::serde::ReflectDeserializer,
+    utils::{HashMap, HashSet},
+};
+use rerun::{
+    external::arrow2::{
+        array::{Array, ListArray},
+        datatypes::DataType,
+    },
+    external::re_log_types::NonMinI64,
+    log::{Chunk, RowId},
+    time::TimeInt,
+    Loggable as _,
+};
+
+// ---
+
+/// A point in time on one of the timelines of a recording.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RerunReplayTime {
+    /// On the `sim_time` timeline, in seconds.
+    SimTime(f64),
+
+    /// On the `frame` timeline, see [`crate::RerunTimelines::frame`].
+    Frame(i64),
+}
+
+impl RerunReplayTime {
+    fn timeline(&self) -> rerun::Timeline {
+        match self {
+            Self::SimTime(_) => rerun::Timeline::new_temporal("sim_time"),
+            Self::Frame(_) => rerun::Timeline::new_sequence("frame"),
+        }
+    }
+
+    fn time(&self) -> TimeInt {
+        match *self {
+            Self::SimTime(secs) => NonMinI64::new((secs * 1e9).round() as i64)
+                .map_or(TimeInt::MIN, TimeInt::from_nanos),
+            Self::Frame(frame) => {
+                NonMinI64::new(frame).map_or(TimeInt::MIN, TimeInt::from_sequence)
+            }
+        }
+    }
+}
+
+/// Send this event to restore the world to its recorded state at the specified point in time.
+///
+/// Only has an effect if a [`RerunReplayPlugin`] was added.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct RerunReplayRequest(pub RerunReplayTime);
+
+// ---
+
+/// Restores a Bevy world from a recording written by [`crate::RerunPlugin`].
+///
+/// What gets restored:
+/// * entities, and their hierarchy (using the `Parent` data),
+/// * their `Transform`s,
+/// * all their reflected components, as long as they were logged as RON (i.e. they didn't have a
+///   dedicated logger) and their types are registered with the `AppTypeRegistry`,
+/// * optionally, all reflected resources, under the same conditions.
+///
+/// Only the entities that are known to the recording are affected: recorded entities that are
+/// still alive get updated in place, dead ones get despawned, and missing ones get spawned.
+/// Everything else is left untouched.
+pub struct RerunReplayPlugin {
+    /// The `.rrd` file to restore from.
+    pub path: PathBuf,
+
+    /// If set, the world is restored to that point in time on startup.
+    ///
+    /// Use [`RerunReplayRequest`] to restore the world at any point afterwards.
+    pub at: Option<RerunReplayTime>,
+
+    /// Whether resources get restored too.
+    ///
+    /// Disabled by default: restoring engine resources (e.g. `Time`) tends to do more harm than
+    /// good.
+    pub resources: bool,
+}
+
+impl RerunReplayPlugin {
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            at: None,
+            resources: false,
+        }
+    }
+
+    /// Restore the world to that point in time on startup.
+    #[inline]
+    pub fn at(mut self, at: RerunReplayTime) -> Self {
+        self.at = Some(at);
+        self
+    }
+
+    /// Whether resources get restored too.
+    #[inline]
+    pub fn with_resources(mut self, resources: bool) -> Self {
+        self.resources = resources;
+        self
+    }
+}
+
+impl Plugin for RerunReplayPlugin {
+    fn build(&self, app: &mut App) {
+        let recording = match RerunRecording::from_rrd_filepath(&self.path) {
+            Ok(recording) => recording,
+            Err(err) => {
+                error!(path = ?self.path, %err, "failed to load recording, replay disabled");
+                return;
+            }
+        };
+
+        app.insert_resource(RerunReplayState {
+            recording,
+            resources: self.resources,
+            entities: Default::default(),
+        })
+        .add_event::<RerunReplayRequest>()
+        .add_systems(First, system_replay);
+
+        if let Some(at) = self.at {
+            app.world_mut().send_event(RerunReplayRequest(at));
+        }
+    }
+}
+
+// ---
+
+/// All the chunks of a recording, in memory.
+#[derive(Default)]
+pub(crate) struct RerunRecording {
+    /// Indexed by entity path, in logging order.
+    chunks: HashMap<rerun::EntityPath, Vec<Chunk>>,
+}
+
+impl RerunRecording {
+    pub fn from_rrd_filepath(path: &Path) -> rerun::external::anyhow::Result<Self> {
+        let file = std::io::BufReader::new(std::fs::File::open(path)?);
+        let decoder =
+            re_log_encoding::decoder::Decoder::new(re_log_encoding::VersionPolicy::Warn, file)?;
+
+        let mut recording = Self::default();
+        for msg in decoder {
+            recording.insert(msg?)?;
+        }
+
+        Ok(recording)
+    }
+
+    /// Keeps track of the data in `msg`, if any.
+    pub fn insert(&mut self, msg: rerun::log::LogMsg) -> rerun::log::ChunkResult<()> {
+        if let rerun::log::LogMsg::ArrowMsg(store_id, msg) = msg {
+            if store_id.kind == rerun::StoreKind::Recording {
+                let chunk = Chunk::from_arrow_msg(&msg)?;
+                self.chunks
+                    .entry(chunk.entity_path().clone())
+                    .or_default()
+                    .push(chunk);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// All the chunks, in logging order for any given entity path.
+    #[inline]
+    pub fn chunks(&self) -> impl Iterator<Item = &Chunk> {
+        self.chunks.values().flatten()
+    }
+
+    /// Whether anything was ever logged at `entity_path`.
+    #[inline]
+    fn contains(&self, entity_path: &rerun::EntityPath) -> bool {
+        self.chunks.contains_key(entity_path)
+    }
+
+    /// Computes the state of every entity at the specified point in time.
+    fn latest_at(&self, at: RerunReplayTime) -> HashMap<rerun::EntityPath, RecordedEntity> {
+        let _trace = info_span!("latest_at").entered();
+
+        let timeline = at.timeline();
+        let query = (at.time(), RowId::MAX);
+
+        let mut entities: HashMap<rerun::EntityPath, RecordedEntity> = HashMap::default();
+        let mut clears: Vec<(rerun::EntityPath, RowIndex, bool)> = Vec::new();
+
+        for chunk in self.chunks() {
+            let indices = chunk.iter_indices(&timeline).collect::<Vec<_>>();
+            if indices.is_empty() {
+                continue;
+            }
+
+            for (component_name, list_array) in
+                chunk
+                    .components()
+                    .iter()
+                    .filter_map(|(component_name, per_desc)| {
+                        per_desc
+                            .values()
+                            .next()
+                            .map(|list_array| (*component_name, list_array))
+                    })
+            {
+                let is_clear = component_name
+                    == <rerun::components::ClearIsRecursive as rerun::Component>::name();
+
+                for (row, index) in latest_rows(list_array, &indices, query) {
+                    let cell = list_array.value(row);
+
+                    if is_clear {
+                        let recursive = rerun::components::ClearIsRecursive::from_arrow2(&*cell)
+                            .ok()
+                            .and_then(|recursive| recursive.first().map(|r| r.0 .0))
+                            .unwrap_or_default();
+                        clears.push((chunk.entity_path().clone(), index, recursive));
+                        continue;
+                    }
+
+                    let components = &mut entities
+                        .entry(chunk.entity_path().clone())
+                        .or_default()
+                        .components;
+                    let is_newer = components
+                        .get(&component_name)
+                        .map_or(true, |(other, _)| index > *other);
+                    if is_newer {
+                        components.insert(component_name, (index, cell));
+                    }
+                }
+            }
+        }
+
+        // Drop everything that was cleared after it was last logged.
+        for (clear_path, clear_index, recursive) in clears {
+            for (entity_path, entity) in &mut entities {
+                let is_affected = *entity_path == clear_path
+                    || (recursive && entity_path.is_descendant_of(&clear_path));
+                if is_affected {
+                    entity
+                        .components
+                        .retain(|_, (index, _)| *index > clear_index);
+                }
+            }
+        }
+
+        entities.retain(|_, entity| !entity.components.is_empty());
+
+        entities
+    }
+}
+
+/// Yields the indices of all valid rows that come before `query`.
+fn latest_rows<'a>(
+    list_array: &'a ListArray<i32>,
+    indices: &'a [RowIndex],
+    query: RowIndex,
+) -> impl Iterator<Item = (usize, RowIndex)> + 'a {
+    indices
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(move |(row, index)| *index <= query && list_array.is_valid(*row))
+}
+
+/// Where a row sits on the queried timeline.
+type RowIndex = (TimeInt, RowId);
+
+/// The state of a recorded entity at a given point in time.
+#[derive(Default)]
+struct RecordedEntity {
+    components: HashMap<rerun::ComponentName, (RowIndex, Box<dyn Array>)>,
+}
+
+impl RecordedEntity {
+    fn get<C: rerun::Component>(&self) -> Option<C> {
+        self.components
+            .get(&<C as rerun::Component>::name())
+            .and_then(|(_, cell)| C::from_arrow2(&**cell).ok())
+            .and_then(|data| data.into_iter().next())
+    }
+
+    /// Returns the RON bodies of all the components that were logged using reflection.
+    fn reflected(&self) -> impl Iterator<Item = String> + '_ {
+        self.components
+            .iter()
+            .filter(|(component_name, (_, cell))| {
+                !component_name.starts_with("rerun.") && cell.data_type() == &DataType::Utf8
+            })
+            .filter_map(|(_, (_, cell))| rerun::components::Text::from_arrow2(&**cell).ok())
+            .filter_map(|text| text.into_iter().next())
+            .map(|text| text.0.to_string())
+    }
+
+    fn transform(&self) -> Option<Transform> {
+        let translation = self.get::<rerun::components::Translation3D>()?;
+        let rotation = self.get::<rerun::components::RotationQuat>();
+        let scale = self.get::<rerun::components::Scale3D>();
+
+        Some(Transform {
+            translation: Vec3::from_array(translation.0 .0),
+            rotation: rotation.map_or(Quat::IDENTITY, |rotation| Quat::from_array(rotation.0 .0)),
+            scale: scale.map_or(Vec3::ONE, |scale| Vec3::from_array(scale.0 .0)),
+        })
+    }
+
+    fn parent(&self) -> Option<rerun::EntityPath> {
+        self.components
+            .get(&rerun::ComponentName::from("Parent"))
+            .and_then(|(_, cell)| rerun::components::Text::from_arrow2(&**cell).ok())
+            .and_then(|text| text.into_iter().next())
+            .map(|text| rerun::EntityPath::parse_forgiving(text.as_str()))
+    }
+}
+
+// ---
+
+#[derive(Resource)]
+struct RerunReplayState {
+    recording: RerunRecording,
+    resources: bool,
+
+    /// The entities that were spawned by previous restorations, so they can be reused.
+    entities: HashMap<rerun::EntityPath, Entity>,
+}
+
+fn system_replay(world: &mut World) {
+    let Some(RerunReplayRequest(at)) = world
+        .resource_mut::<Events<RerunReplayRequest>>()
+        .drain()
+        .last()
+    else {
+        return;
+    };
+
+    world.resource_scope(|world, mut state: Mut<'_, RerunReplayState>| {
+        restore(world, &mut state, at);
+    });
+}
+
+/// Restores the world to its recorded state at the specified point in time.
+fn restore(world: &mut World, state: &mut RerunReplayState, at: RerunReplayTime) {
+    let _trace = info_span!("restore").entered();
+
+    let recorded = state.recording.latest_at(at);
+
+    let is_entity = |entity_path: &rerun::EntityPath| {
+        let parts = entity_path.as_slice();
+        parts.len() > 1
+            && parts[0].unescaped_str() == "world"
+            && parts.iter().all(|part| part.unescaped_str() != "comps")
+    };
+    let is_resource = |entity_path: &rerun::EntityPath| {
+        let parts = entity_path.as_slice();
+        parts.len() == 2 && parts[0].unescaped_str() == "resources"
+    };
+
+    // Map every known entity path to a live entity, if any.
+    let mut entities = {
+        let mut live = world.query::<(Entity, &crate::RerunEntityPath)>();
+        live.iter(world)
+            .map(|(entity_id, entity_path)| (entity_path.0.clone(), entity_id))
+            .chain(state.entities.iter().map(|(path, id)| (path.clone(), *id)))
+            .filter(|(_, entity_id)| world.get_entity(*entity_id).is_ok())
+            .collect::<HashMap<_, _>>()
+    };
+
+    // Despawn everything that the recording knows about but isn't alive at that point in time.
+    let alive = recorded
+        .keys()
+        .filter(|entity_path| is_entity(entity_path))
+        .collect::<HashSet<_>>();
+    let survivors = alive
+        .iter()
+        .filter_map(|entity_path| entities.get(*entity_path).copied())
+        .collect::<HashSet<_>>();
+    for (entity_path, entity_id) in &entities {
+        let is_dead = !alive.contains(entity_path) && state.recording.contains(entity_path);
+        if is_dead && world.get_entity(*entity_id).is_ok() {
+            // NOTE: Children that are alive at that point in time must outlive their parent: the
+            // hierarchy gets restored below anyway.
+            let children = world
+                .get::<Children>(*entity_id)
+                .map(|children| children.to_vec())
+                .unwrap_or_default();
+            for child_id in children {
+                if survivors.contains(&child_id) {
+                    world.entity_mut(child_id).remove_parent();
+                }
+            }
+
+            world.entity_mut(*entity_id).despawn_recursive();
+        }
+    }
+    entities.retain(|_, entity_id| world.get_entity(*entity_id).is_ok());
+    state
+        .entities
+        .retain(|_, entity_id| world.get_entity(*entity_id).is_ok());
+
+    let type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = type_registry.read();
+
+    // Spawn the missing entities and restore their components.
+    for (entity_path, entity) in recorded.iter().filter(|(path, _)| is_entity(path)) {
+        let entity_id = *entities
+            .entry(entity_path.clone())
+            .or_insert_with(|| world.spawn_empty().id());
+        state.entities.insert(entity_path.clone(), entity_id);
+
+        let mut entity_mut = world.entity_mut(entity_id);
+
+        if let Some(transform) = entity.transform() {
+            entity_mut.insert(transform);
+        }
+
+        for body in entity.reflected() {
+            let Some((reflected, registration)) = deserialize_reflected(&type_registry, &body)
+            else {
+                continue;
+            };
+            if let Some(reflect_component) = registration.data::<ReflectComponent>() {
+                reflect_component.insert(&mut entity_mut, &*reflected, &type_registry);
+            }
+        }
+    }
+
+    // Restore the hierarchy, now that every entity exists.
+    for (entity_path, entity) in recorded.iter().filter(|(path, _)| is_entity(path)) {
+        let entity_id = entities[entity_path];
+        let parent_id = entity
+            .parent()
+            .and_then(|parent_path| entities.get(&parent_path).copied());
+
+        let mut entity_mut = world.entity_mut(entity_id);
+        match parent_id {
+            Some(parent_id) => {
+                entity_mut.set_parent(parent_id);
+            }
+            None => {
+                entity_mut.remove_parent();
+            }
+        }
+    }
+
+    if state.resources {
+        for (_, resource) in recorded.iter().filter(|(path, _)| is_resource(path)) {
+            for body in resource.reflected() {
+                let Some((reflected, registration)) = deserialize_reflected(&type_registry, &body)
+                else {
+                    continue;
+                };
+                if let Some(reflect_resource) = registration.data::<ReflectResource>() {
+                    reflect_resource.insert(world, &*reflected, &type_registry);
+                }
+            }
+        }
+    }
+}
+
+/// Deserializes RON data written by the reflection-based logger.
+fn deserialize_reflected<'a>(
+    type_registry: &'a bevy::reflect::TypeRegistry,
+    body: &str,
+) -> Option<(Box<dyn PartialReflect>, &'a bevy::reflect::TypeRegistration)> {
+    let reflected = ron::Options::default()
+        .from_str_seed(body, ReflectDeserializer::new(type_registry))
+        .ok()?;
+
+    let registration = reflected
+        .get_represented_type_info()
+        .and_then(|info| type_registry.get(info.type_id()))?;
+
+    Some((reflected, registration))
+}
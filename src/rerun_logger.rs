@@ -1,9 +1,11 @@
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use bevy::{
-    ecs::component::ComponentInfo,
+    asset::UntypedAssetId,
+    ecs::component::{ComponentId, ComponentInfo},
     prelude::*,
-    reflect::{serde::ReflectSerializer, ReflectFromPtr},
+    ptr::Ptr,
+    reflect::{serde::ReflectSerializer, FromType, ReflectFromPtr, TypeRegistry},
     utils::HashMap,
 };
 
@@ -11,9 +13,9 @@ use crate::DefaultRerunComponentLoggers;
 
 // ---
 
-// TODO(cmc): this should really work with component ids, although the API gotta uses names...
-// but that means doing things (such as defaults) lazily since components are themselves registered
-// lazily... and then it becomes a mess.
+// TODO(cmc): the default loggers should really work with component ids too, although that means
+// doing things lazily since components are themselves registered lazily... and then it becomes a
+// mess. See `RerunTypedComponentLoggers` for the user-facing equivalent.
 
 /// The callback type to create a [`RerunLogger`].
 pub trait RerunLoggerFn:
@@ -58,14 +60,80 @@ impl std::ops::Deref for BoxedOrStaticRerunLogger {
     }
 }
 
+/// The callback type to log a resource with a [`RerunLogger`], see [`RerunLogger::new_resource`].
+///
+/// The resource itself can be retrieved from the `World`.
+pub trait RerunResourceLoggerFn:
+    Send
+    + Sync
+    + for<'w> Fn(
+        &'w World,
+        &'w ComponentInfo,
+    ) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>)
+{
+}
+
+impl<F> RerunResourceLoggerFn for F where
+    F: Send
+        + Sync
+        + for<'w> Fn(
+            &'w World,
+            &'w ComponentInfo,
+        ) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>)
+{
+}
+
+#[derive(Clone)]
+pub enum BoxedOrStaticRerunResourceLogger {
+    Boxed(Arc<dyn RerunResourceLoggerFn>),
+    Static(&'static dyn RerunResourceLoggerFn),
+}
+
+impl std::ops::Deref for BoxedOrStaticRerunResourceLogger {
+    type Target = dyn RerunResourceLoggerFn;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match self {
+            BoxedOrStaticRerunResourceLogger::Boxed(f) => &**f,
+            BoxedOrStaticRerunResourceLogger::Static(f) => f,
+        }
+    }
+}
+
+/// The callback type to declare which assets a [`RerunLogger`] reads.
+///
+/// See [`RerunLogger::with_asset_dependencies`].
+pub trait RerunAssetDependenciesFn:
+    Send + Sync + Fn(&World, EntityRef<'_>) -> Vec<UntypedAssetId>
+{
+}
+
+impl<F> RerunAssetDependenciesFn for F where
+    F: Send + Sync + Fn(&World, EntityRef<'_>) -> Vec<UntypedAssetId>
+{
+}
+
 /// An arbitrary callback to convert Bevy component data into Rerun component data.
+///
+/// The same logger can also handle resources of the same type, see [`Self::with_resource`].
 #[derive(Resource, Deref, Clone)]
-pub struct RerunLogger(BoxedOrStaticRerunLogger);
+pub struct RerunLogger {
+    #[deref]
+    f: BoxedOrStaticRerunLogger,
+
+    asset_dependencies: Option<Arc<dyn RerunAssetDependenciesFn>>,
+
+    resource: Option<BoxedOrStaticRerunResourceLogger>,
+
+    /// Where the data ends up, if known ahead of time, see [`Self::suffix`].
+    suffix: Option<&'static str>,
+}
 
 impl std::fmt::Debug for RerunLogger {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_tuple("RerunLogger")
-            .field(&format!("{:p}", &self.0) as _)
+            .field(&format!("{:p}", &self.f) as _)
             .finish()
     }
 }
@@ -76,13 +144,187 @@ impl RerunLogger {
     where
         F: RerunLoggerFn + 'static,
     {
-        Self(BoxedOrStaticRerunLogger::Boxed(Arc::new(f) as _))
+        Self {
+            f: BoxedOrStaticRerunLogger::Boxed(Arc::new(f) as _),
+            asset_dependencies: None,
+            resource: None,
+            suffix: None,
+        }
     }
 
     #[inline]
     pub const fn new_static(f: &'static dyn RerunLoggerFn) -> Self {
-        Self(BoxedOrStaticRerunLogger::Static(f))
+        Self {
+            f: BoxedOrStaticRerunLogger::Static(f),
+            asset_dependencies: None,
+            resource: None,
+            suffix: None,
+        }
+    }
+
+    /// Creates a logger that only handles resources.
+    ///
+    /// See [`Self::with_resource`].
+    #[inline]
+    pub fn new_resource<F>(f: F) -> Self
+    where
+        F: RerunResourceLoggerFn + 'static,
+    {
+        Self::new_static(&log_nothing).with_resource(f)
     }
+
+    /// Also uses `f` to log resources of the same type, under `resources/<type_name>`.
+    ///
+    /// Loggers without one don't log anything for resources.
+    #[inline]
+    pub fn with_resource(mut self, f: impl RerunResourceLoggerFn + 'static) -> Self {
+        self.resource = Some(BoxedOrStaticRerunResourceLogger::Boxed(Arc::new(f)));
+        self
+    }
+
+    /// Whether this logger knows how to log resources, see [`Self::with_resource`].
+    #[inline]
+    pub fn handles_resources(&self) -> bool {
+        self.resource.is_some()
+    }
+
+    /// Logs `resource`, see [`Self::with_resource`].
+    pub fn log_resource(
+        &self,
+        world: &World,
+        resource: &ComponentInfo,
+    ) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>) {
+        self.resource
+            .as_ref()
+            .map_or((None, None), |f| f(world, resource))
+    }
+
+    /// Declares which assets this logger reads for a given entity, so that the component gets
+    /// logged again whenever one of them is added or modified.
+    ///
+    /// Only the asset types registered with [`crate::RerunAppExt::register_rerun_asset`] are
+    /// tracked. `Image`, `Mesh`, `StandardMaterial` and `ColorMaterial` always are.
+    #[inline]
+    pub fn with_asset_dependencies(mut self, f: impl RerunAssetDependenciesFn + 'static) -> Self {
+        self.asset_dependencies = Some(Arc::new(f));
+        self
+    }
+
+    /// The assets this logger reads for `entity`, see [`Self::with_asset_dependencies`].
+    pub fn asset_dependencies(&self, world: &World, entity: EntityRef<'_>) -> Vec<UntypedAssetId> {
+        self.asset_dependencies
+            .as_ref()
+            .map(|f| f(world, entity))
+            .unwrap_or_default()
+    }
+
+    /// Creates a logger for a specific component type `C`, which is logged at the entity path of
+    /// its entity.
+    ///
+    /// Nothing is logged if `f` returns `None`.
+    pub fn typed<C, D>(
+        f: impl Fn(&C, &RerunLoggerContext<'_>) -> Option<D> + Send + Sync + 'static,
+    ) -> Self
+    where
+        C: Component,
+        D: rerun::AsComponents + 'static,
+    {
+        Self::new(move |world, all_entities, entity, component| {
+            let data = entity.get::<C>().and_then(|data| {
+                let ctx = RerunLoggerContext {
+                    world,
+                    all_entities,
+                    entity: world.entity(entity.id()),
+                    component,
+                };
+                f(data, &ctx)
+            });
+
+            (None, data.map(|data| Box::new(data) as _))
+        })
+    }
+
+    /// Creates a logger for a specific resource type `R`, which is logged at
+    /// `resources/<type_name>`.
+    ///
+    /// Nothing is logged if `f` returns `None`.
+    pub fn typed_resource<R, D>(f: impl Fn(&R, &World) -> Option<D> + Send + Sync + 'static) -> Self
+    where
+        R: Resource,
+        D: rerun::AsComponents + 'static,
+    {
+        Self::new_resource(move |world, _resource| {
+            let data = world.get_resource::<R>().and_then(|data| f(data, world));
+            (None, data.map(|data| Box::new(data) as _))
+        })
+    }
+
+    /// The suffix this logger logs at, if known ahead of time.
+    ///
+    /// See [`Self::with_suffix`] and [`RerunLog::SUFFIX`].
+    #[inline]
+    pub fn suffix(&self) -> Option<&'static str> {
+        self.suffix
+    }
+
+    /// Logs whatever this logger returns at `<entity_path>/comps/<suffix>` instead.
+    pub fn with_suffix(self, suffix: &'static str) -> Self {
+        let asset_dependencies = self.asset_dependencies.clone();
+        let resource = self.resource.clone().map(|f| {
+            BoxedOrStaticRerunResourceLogger::Boxed(Arc::new(move |world, resource| {
+                let (_, data) = f(world, resource);
+                (Some(suffix), data)
+            }))
+        });
+        Self {
+            asset_dependencies,
+            resource,
+            suffix: Some(suffix),
+            ..Self::new(move |world, all_entities, entity, component| {
+                let (_, data) = self(world, all_entities, entity, component);
+                (Some(suffix), data)
+            })
+        }
+    }
+
+    /// Logs the component (or resource) as RON, via reflection.
+    ///
+    /// This is what's used for components and resources that no other logger knows about.
+    pub fn reflected() -> Self {
+        LOG_REFLECTED.clone()
+    }
+
+    /// Creates a logger for a component that implements [`RerunLog`].
+    pub fn from_rerun_log<C: Component + RerunLog>() -> Self {
+        Self {
+            suffix: C::SUFFIX,
+            ..Self::new(|_world, _all_entities, entity, _component| {
+                let data = entity.get::<C>().and_then(C::to_rerun_log);
+                (C::SUFFIX, data)
+            })
+        }
+    }
+}
+
+/// Components that know how to convert themselves into Rerun data.
+///
+/// Usually derived, see [`macro@crate::RerunLog`], and registered with
+/// [`crate::RerunAppExt::register_rerun_log`].
+pub trait RerunLog: Send + Sync + 'static {
+    /// Logs the data at `<entity_path>/comps/<suffix>` rather than at the entity path itself.
+    const SUFFIX: Option<&'static str> = None;
+
+    fn to_rerun_log(&self) -> Option<Box<dyn rerun::AsComponents>>;
+}
+
+/// Everything a typed [`RerunLogger`] has access to, on top of the component data itself.
+///
+/// See [`RerunLogger::typed`].
+pub struct RerunLoggerContext<'w> {
+    pub world: &'w World,
+    pub all_entities: &'w QueryState<(Entity, Option<&'w Parent>, Option<&'w Name>)>,
+    pub entity: EntityRef<'w>,
+    pub component: &'w ComponentInfo,
 }
 
 // ---
@@ -91,6 +333,9 @@ impl RerunLogger {
 ///
 /// E.g. log `"bevy_transform::components::transform::Transform"` as [`rerun::Transform3D`].
 ///
+/// Resources are looked up by their fully-qualified type name in there too, see
+/// [`RerunLogger::with_resource`].
+///
 /// Use `None` to prevent the data from being logged entirely.
 ///
 /// Don't set anything if you want to let the default logger to take over.
@@ -106,11 +351,244 @@ impl RerunComponentLoggers {
     }
 }
 
+/// An ordered list of [`RerunLogger`]s, which all run for the same component.
+///
+/// Each logger can log at its own suffix (see [`RerunLogger::with_suffix`]), which makes it
+/// possible to keep the builtin visualization of a component and add overlays on top of it.
+#[derive(Clone, Debug)]
+pub struct RerunLoggerChain {
+    /// Whether the logger that would otherwise be used runs first, i.e. the one from
+    /// [`RerunComponentLoggers`], [`DefaultRerunComponentLoggers`], [`ReflectRerunLogger`] or the
+    /// RON fallback, in that order.
+    pub builtin: bool,
+
+    /// Runs first instead of the builtin logger, if [`Self::builtin`] is disabled.
+    pub replacement: Option<RerunLogger>,
+
+    /// Runs after the builtin logger, in order.
+    pub loggers: Vec<RerunLogger>,
+
+    /// Whether the component is also logged as RON, see [`RerunLogger::reflected`].
+    pub reflected: bool,
+}
+
+impl Default for RerunLoggerChain {
+    fn default() -> Self {
+        Self {
+            builtin: true,
+            replacement: None,
+            loggers: Vec::new(),
+            reflected: false,
+        }
+    }
+}
+
+impl RerunLoggerChain {
+    /// Only runs `logger`, instead of the builtin logger.
+    pub fn replace(logger: RerunLogger) -> Self {
+        Self::disabled().with_replacement(logger)
+    }
+
+    /// Doesn't log anything at all.
+    pub fn disabled() -> Self {
+        Self {
+            builtin: false,
+            replacement: None,
+            loggers: Vec::new(),
+            reflected: false,
+        }
+    }
+
+    /// Runs `logger` instead of the builtin logger, while keeping all the other loggers.
+    pub fn with_replacement(mut self, logger: RerunLogger) -> Self {
+        self.set_replacement(logger);
+        self
+    }
+
+    /// Runs `logger` instead of the builtin logger, while keeping all the other loggers.
+    pub fn set_replacement(&mut self, logger: RerunLogger) {
+        self.builtin = false;
+        self.replacement = Some(logger);
+    }
+
+    /// Runs `logger` after all the loggers already in the chain.
+    pub fn with(mut self, logger: RerunLogger) -> Self {
+        self.push(logger);
+        self
+    }
+
+    /// Runs `logger` after all the loggers already in the chain.
+    ///
+    /// Warns if another logger in the chain is already known to log at the same suffix, since
+    /// they would then overwrite each other's data.
+    pub fn push(&mut self, logger: RerunLogger) {
+        if let Some(suffix) = logger.suffix() {
+            let is_taken = self
+                .replacement
+                .iter()
+                .chain(&self.loggers)
+                .any(|other| other.suffix() == Some(suffix));
+            if is_taken {
+                warn!(
+                    "several Rerun loggers log at the same suffix ({suffix:?}), \
+                     they will overwrite each other"
+                );
+            }
+        }
+
+        self.loggers.push(logger);
+    }
+
+    /// Also logs the component as RON, see [`RerunLogger::reflected`].
+    pub fn with_reflected(mut self) -> Self {
+        self.reflected = true;
+        self
+    }
+}
+
+/// Associate a [`RerunLoggerChain`] with a component, by [`ComponentId`].
+///
+/// Unlike [`RerunComponentLoggers`], this keeps working when a component moves to another module.
+///
+/// Usually populated through [`crate::RerunAppExt::register_rerun_logger`] and friends.
+#[derive(Resource, Deref, DerefMut, Clone, Default)]
+pub struct RerunTypedComponentLoggers(pub HashMap<ComponentId, RerunLoggerChain>);
+
+/// Type data that lets a reflected component ship its own [`RerunLogger`].
+///
+/// Register it with `#[reflect(RerunLogger)]`, on any component that implements [`RerunLog`]:
+/// ```rust,ignore
+/// #[derive(Component, Reflect, RerunLog)]
+/// #[reflect(Component, RerunLogger)]
+/// #[rerun(archetype = Points3D)]
+/// struct Waypoint(#[rerun(positions)] Vec3);
+/// ```
+///
+/// Only used if neither [`RerunTypedComponentLoggers`], [`RerunComponentLoggers`] nor
+/// [`DefaultRerunComponentLoggers`] have anything to say about the component.
+#[derive(Deref, Clone)]
+pub struct ReflectRerunLogger(RerunLogger);
+
+impl<C: Component + RerunLog> FromType<C> for ReflectRerunLogger {
+    fn from_type() -> Self {
+        Self(RerunLogger::from_rerun_log::<C>())
+    }
+}
+
+/// Caches the [`ReflectRerunLogger`] of every component known to the world, by [`ComponentId`].
+///
+/// This way the [`AppTypeRegistry`] doesn't have to be locked for every component of every entity
+/// on every frame. Kept up to date by the sync systems, see [`Self::update`].
+#[derive(Resource, Clone, Default)]
+pub struct RerunReflectedComponentLoggers(HashMap<ComponentId, Option<RerunLogger>>);
+
+impl RerunReflectedComponentLoggers {
+    /// Looks up the [`ReflectRerunLogger`] of all the components that were registered since the
+    /// last update.
+    pub fn update(&mut self, world: &World) {
+        let components = world.components();
+
+        // NOTE: Component IDs are never recycled, so if the count hasn't changed, neither have
+        // the components.
+        if self.0.len() == components.len() {
+            return;
+        }
+
+        let type_registry = world.resource::<AppTypeRegistry>().read();
+        for component in components.iter() {
+            self.0.entry(component.id()).or_insert_with(|| {
+                component.type_id().and_then(|type_id| {
+                    type_registry
+                        .get_type_data::<ReflectRerunLogger>(type_id)
+                        .map(|logger| logger.0.clone())
+                })
+            });
+        }
+    }
+
+    /// Returns the [`ReflectRerunLogger`] of `component`, if it has one.
+    pub fn get(&self, component: ComponentId) -> Option<&RerunLogger> {
+        self.0.get(&component).and_then(Option::as_ref)
+    }
+}
+
+/// Returns all the loggers to run for `component`, in order.
+///
+/// See [`RerunLoggerChain`].
+pub fn get_component_loggers<'a>(
+    component: &ComponentInfo,
+    typed_loggers: Option<&'a RerunTypedComponentLoggers>,
+    loggers: Option<&'a RerunComponentLoggers>,
+    default_loggers: &'a DefaultRerunComponentLoggers,
+    reflected_loggers: &'a RerunReflectedComponentLoggers,
+) -> impl Iterator<Item = Cow<'a, RerunLogger>> {
+    let chain = typed_loggers.and_then(|loggers| loggers.get(&component.id()));
+
+    let (builtin, is_fallback) = if chain.map_or(true, |chain| chain.builtin) {
+        match get_builtin_component_logger(component, loggers, default_loggers, reflected_loggers) {
+            Some(logger) => (logger, false),
+            None => (Some(Cow::Borrowed(&LOG_REFLECTED)), true),
+        }
+    } else {
+        (
+            chain
+                .and_then(|chain| chain.replacement.as_ref())
+                .map(Cow::Borrowed),
+            false,
+        )
+    };
+
+    // NOTE: No need to log the same RON twice.
+    let reflected = chain.is_some_and(|chain| chain.reflected) && !is_fallback;
+
+    builtin
+        .into_iter()
+        .chain(
+            chain
+                .into_iter()
+                .flat_map(|chain| chain.loggers.iter().map(Cow::Borrowed)),
+        )
+        .chain(reflected.then_some(Cow::Borrowed(&LOG_REFLECTED)))
+}
+
+/// Returns the main logger for `component`, or `None` if it was explicitly disabled.
+///
+/// Typed and reflected loggers aren't taken into account.
+#[deprecated(
+    since = "0.21.0",
+    note = "use `get_component_loggers`, which also returns the loggers chained with `RerunTypedComponentLoggers`"
+)]
 pub fn get_component_logger<'a>(
     component: &ComponentInfo,
     loggers: Option<&'a RerunComponentLoggers>,
     default_loggers: &'a DefaultRerunComponentLoggers,
 ) -> Option<&'a RerunLogger> {
+    static NO_REFLECTED_LOGGERS: std::sync::LazyLock<RerunReflectedComponentLoggers> =
+        std::sync::LazyLock::new(Default::default);
+
+    get_component_loggers(
+        component,
+        None,
+        loggers,
+        default_loggers,
+        &NO_REFLECTED_LOGGERS,
+    )
+    .next()
+    // NOTE: Without typed loggers, all loggers are borrowed.
+    .and_then(|logger| match logger {
+        Cow::Borrowed(logger) => Some(logger),
+        Cow::Owned(_) => None,
+    })
+}
+
+/// Returns `None` if no logger was specified at all, or `Some(None)` if the component was
+/// explicitly disabled.
+fn get_builtin_component_logger<'a>(
+    component: &ComponentInfo,
+    loggers: Option<&'a RerunComponentLoggers>,
+    default_loggers: &'a DefaultRerunComponentLoggers,
+    reflected_loggers: &'a RerunReflectedComponentLoggers,
+) -> Option<Option<Cow<'a, RerunLogger>>> {
     let component_name = rerun::ComponentName::from(component.name());
 
     if let Some(logger) = loggers.and_then(|loggers| {
@@ -119,7 +597,7 @@ pub fn get_component_logger<'a>(
             .as_ref()
             .map(|logger| logger.as_ref())
     }) {
-        return logger;
+        return Some(logger.map(Cow::Borrowed));
     }
 
     if let Some(logger) = default_loggers
@@ -127,35 +605,67 @@ pub fn get_component_logger<'a>(
         .as_ref()
         .map(|logger| logger.as_ref())
     {
-        return logger;
+        return Some(logger.map(Cow::Borrowed));
     }
 
-    #[allow(clippy::unnecessary_wraps)]
-    fn log_ignored_component(
-        world: &World,
-        _all_entities: &QueryState<(Entity, Option<&Parent>, Option<&Name>)>,
-        entity: EntityRef<'_>,
-        component: &ComponentInfo,
-    ) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>) {
-        let name = component.name();
-        let body = component_to_ron(world, entity, component)
-            .unwrap_or_else(|| "<missing reflection metadata>".into());
-        let reflected = Aliased::<rerun::components::Text>::new(name.replace("::", "."), body);
+    reflected_loggers
+        .get(component.id())
+        .map(|logger| Some(Cow::Borrowed(logger)))
+}
 
-        (None, Some(Box::new(reflected) as _))
-    }
+#[allow(clippy::unnecessary_wraps)]
+fn log_reflected_component(
+    world: &World,
+    _all_entities: &QueryState<(Entity, Option<&Parent>, Option<&Name>)>,
+    entity: EntityRef<'_>,
+    component: &ComponentInfo,
+) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>) {
+    let name = component.name();
+    let body = entity
+        .get_by_id(component.id())
+        .ok()
+        .and_then(|ptr| component_to_ron(world, component, ptr))
+        .unwrap_or_else(|| "<missing reflection metadata>".into());
+    let reflected = Aliased::<rerun::components::Text>::new(name.replace("::", "."), body);
+
+    (None, Some(Box::new(reflected) as _))
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn log_reflected_resource(
+    world: &World,
+    resource: &ComponentInfo,
+) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>) {
+    let name = resource.name();
+    let body = world
+        .get_resource_by_id(resource.id())
+        .and_then(|ptr| component_to_ron(world, resource, ptr))
+        .unwrap_or_else(|| "<missing reflection metadata>".into());
+    let reflected = Aliased::<rerun::components::Text>::new(name.replace("::", "."), body);
+
+    (None, Some(Box::new(reflected) as _))
+}
 
-    static LOG_IGNORED_COMPONENT: RerunLogger = RerunLogger::new_static(&log_ignored_component);
+static LOG_REFLECTED: RerunLogger = RerunLogger {
+    f: BoxedOrStaticRerunLogger::Static(&log_reflected_component),
+    asset_dependencies: None,
+    resource: Some(BoxedOrStaticRerunResourceLogger::Static(
+        &log_reflected_resource,
+    )),
+    suffix: None,
+};
 
-    Some(&LOG_IGNORED_COMPONENT)
+fn log_nothing(
+    _world: &World,
+    _all_entities: &QueryState<(Entity, Option<&Parent>, Option<&Name>)>,
+    _entity: EntityRef<'_>,
+    _component: &ComponentInfo,
+) -> (Option<&'static str>, Option<Box<dyn rerun::AsComponents>>) {
+    (None, None)
 }
 
 // TODO(cmc): why does this seem to fail for recursive types though? or is it something else?
-fn component_to_ron(
-    world: &World,
-    entity: EntityRef<'_>,
-    component: &ComponentInfo,
-) -> Option<String> {
+fn component_to_ron(world: &World, component: &ComponentInfo, ptr: Ptr<'_>) -> Option<String> {
     let type_registry = world.resource::<AppTypeRegistry>();
     let type_registry = type_registry.read();
 
@@ -165,19 +675,64 @@ fn component_to_ron(
         .and_then(|ty| ty.data::<ReflectFromPtr>())
         .and_then(|reflect_from_ptr| {
             #[allow(unsafe_code)]
-            let reflected = entity
-                .get_by_id(component.id())
-                // Safety: the type registry cannot be wrong, surely
-                .map(|ptr| unsafe { reflect_from_ptr.as_reflect(ptr) });
-
-            reflected.ok().and_then(|reflected| {
-                let serializer =
-                    ReflectSerializer::new(reflected.as_partial_reflect(), &type_registry);
-                ron::ser::to_string_pretty(&serializer, ron::ser::PrettyConfig::default()).ok()
-            })
+            // Safety: the type registry cannot be wrong, surely
+            let reflected = unsafe { reflect_from_ptr.as_reflect(ptr) };
+
+            reflect_to_ron(reflected.as_partial_reflect(), &type_registry)
         })
 }
 
+pub(crate) fn reflect_to_ron(
+    reflected: &dyn PartialReflect,
+    type_registry: &TypeRegistry,
+) -> Option<String> {
+    let serializer = ReflectSerializer::new(reflected, type_registry);
+    ron::ser::to_string_pretty(&serializer, ron::ser::PrettyConfig::default()).ok()
+}
+
+// ---
+
+/// Returns the logger to use for `resource`, if any.
+///
+/// Resources are looked up by name in [`RerunComponentLoggers`], then in
+/// [`DefaultRerunComponentLoggers`], and are otherwise logged as RON if they are reflected (see
+/// [`RerunLogger::reflected`]).
+pub fn get_resource_logger<'a>(
+    resource: &ComponentInfo,
+    loggers: Option<&'a RerunComponentLoggers>,
+    default_loggers: &'a DefaultRerunComponentLoggers,
+    type_registry: &AppTypeRegistry,
+) -> Option<&'a RerunLogger> {
+    let resource_name = rerun::ComponentName::from(resource.name());
+
+    if let Some(logger) = loggers.and_then(|loggers| {
+        loggers
+            .get(&resource_name)
+            .as_ref()
+            .map(|logger| logger.as_ref())
+    }) {
+        return logger;
+    }
+
+    if let Some(logger) = default_loggers
+        .get(&resource_name)
+        .and_then(Option::as_ref)
+        .filter(|logger| logger.handles_resources())
+    {
+        return Some(logger);
+    }
+
+    // NOTE: Non-reflected resources would only ever show up as placeholders.
+    let is_reflected = resource.type_id().is_some_and(|type_id| {
+        type_registry
+            .read()
+            .get_type_data::<ReflectResource>(type_id)
+            .is_some()
+    });
+
+    is_reflected.then_some(&LOG_REFLECTED)
+}
+
 // ---
 
 // TODO(cmc): Rerun should provide tools for this.
@@ -0,0 +1,96 @@
+use bevy::{ecs::event::EventCursor, prelude::*, state::state::FreelyMutableState};
+use rerun::external::re_log::ResultExt;
+
+use crate::{
+    sync::{set_recording_time, RerunSyncState},
+    Aliased,
+};
+
+// ---
+
+fn state_entity_path<S: States>() -> rerun::EntityPath {
+    rerun::EntityPath::new(vec![
+        rerun::EntityPathPart::new("states"),
+        rerun::EntityPathPart::new(std::any::type_name::<S>()),
+    ])
+}
+
+/// Logs the `S` state at `states/<type_name>`:
+/// * the current state, as a text component, every time it changes,
+/// * every [`StateTransitionEvent<S>`], as a [`rerun::TextLog`].
+///
+/// Works with any kind of state: regular states, sub-states and computed states alike.
+pub(crate) fn system_log_state<S: States>(
+    world: &World,
+    state: Option<Res<'_, State<S>>>,
+    mut transitions: Local<'_, EventCursor<StateTransitionEvent<S>>>,
+    mut had_state: Local<'_, bool>,
+) {
+    let _trace = info_span!("log_state", state = std::any::type_name::<S>()).entered();
+
+    let Some(sync_state) = world.get_resource::<RerunSyncState>() else {
+        return;
+    };
+
+    let rec = &sync_state.rec;
+    set_recording_time(world, rec);
+
+    let entity_path = state_entity_path::<S>();
+
+    if let Some(events) = world.get_resource::<Events<StateTransitionEvent<S>>>() {
+        for StateTransitionEvent { exited, entered } in transitions.read(events) {
+            let body = format!("{exited:?} -> {entered:?}");
+            rec.log(entity_path.clone(), &rerun::TextLog::new(body))
+                .ok_or_log_error();
+        }
+    }
+
+    match state {
+        Some(state) => {
+            if state.is_changed() || sync_state.force_full_sync {
+                let current =
+                    Aliased::<rerun::components::Text>::new("State", format!("{:?}", state.get()));
+                rec.log(entity_path, &current).ok_or_log_error();
+            }
+        }
+
+        // NOTE: Computed states and sub-states come and go.
+        None if *had_state => {
+            rec.log(entity_path, &rerun::Clear::flat())
+                .ok_or_log_error();
+        }
+
+        None => {}
+    }
+
+    *had_state = world.contains_resource::<State<S>>();
+}
+
+/// Logs every state requested through [`NextState<S>`] at `states/<type_name>`, as a
+/// [`rerun::TextLog`].
+///
+/// Only states that can be set manually have a [`NextState`], i.e. not computed states.
+pub(crate) fn system_log_next_state<S: FreelyMutableState>(
+    world: &World,
+    next_state: Option<Res<'_, NextState<S>>>,
+) {
+    let _trace = info_span!("log_next_state", state = std::any::type_name::<S>()).entered();
+
+    let Some(sync_state) = world.get_resource::<RerunSyncState>() else {
+        return;
+    };
+
+    let Some(next_state) = next_state.filter(|next_state| next_state.is_changed()) else {
+        return;
+    };
+    let NextState::Pending(next) = &*next_state else {
+        return;
+    };
+
+    let rec = &sync_state.rec;
+    set_recording_time(world, rec);
+
+    let body = format!("requested: {next:?}");
+    rec.log(state_entity_path::<S>(), &rerun::TextLog::new(body))
+        .ok_or_log_error();
+}
@@ -1,121 +1,458 @@
-use std::hash::Hash;
+use std::marker::PhantomData;
 
 use bevy::{
+    asset::UntypedAssetId,
     core::FrameCount,
     ecs::{
         component::{ComponentId, ComponentInfo},
-        entity::EntityHashMap,
+        entity::{EntityHashMap, EntityHashSet},
         event::EventCursor,
+        query::QueryFilter,
+        removal_detection::RemovedComponentEntity,
+        schedule::{InternedScheduleLabel, InternedSystemSet, ScheduleLabel},
     },
+    hierarchy::HierarchyEvent,
     prelude::*,
-    reflect::{serde::ReflectSerializer, ReflectFromPtr},
-    utils::{AHasher, HashMap},
+    tasks::{ComputeTaskPool, TaskPool},
+    utils::{HashMap, HashSet},
 };
 use rerun::external::re_log::ResultExt;
 
 use crate::{
-    compute_entity_path, get_component_logger, DefaultRerunComponentLoggers, RerunComponentLoggers,
+    assets::{sync_assets, track_asset_changes, RerunAssetsState, RerunChangedAssets},
+    cached_entity_path, compute_entity_path,
+    default_loggers::bevy_point_light_range,
+    entity_filter::synced_entities,
+    get_component_loggers, get_resource_logger,
+    timelines::{system_count_fixed_ticks, RerunFixedTick},
+    ChangeFingerprint, DefaultRerunComponentLoggers, RerunChangeDedup, RerunComponentLoggers,
+    RerunEntityPath, RerunLogger, RerunReflectedComponentLoggers, RerunTimelines,
+    RerunTrackingMode, RerunTypedComponentLoggers,
 };
 
 // ---
 
 #[derive(Resource)]
-struct RerunSyncState {
+pub(crate) struct RerunSyncState {
     /// Where to publish the data?
     pub rec: rerun::RecordingStream,
 
     /// Keeps track of alive entities so we can clear those that get despawned.
     pub entities: EntityHashMap<rerun::EntityPath>,
+
+    /// If set, the next sync will log every single component, whether it changed or not.
+    ///
+    /// Automatically reset once the sync is done.
+    pub force_full_sync: bool,
+
+    /// Keeps track of the hierarchy changes we've already processed.
+    pub hierarchy_events: EventCursor<HierarchyEvent>,
+
+    /// Keeps track of the [`Name`] removals we've already processed.
+    pub removed_names: EventCursor<RemovedComponentEntity>,
+
+    /// Which entities get synced, on top of the plugin's `QueryFilter`.
+    pub tracking_mode: RerunTrackingMode,
+
+    /// Whether resources get synced too.
+    pub sync_resources: bool,
+
+    /// Which timelines the recording is indexed by.
+    pub timelines: RerunTimelines,
+
+    /// Keeps track of alive resources so we can clear those that get removed.
+    pub resources: HashMap<ComponentId, rerun::EntityPath>,
+
+    /// Used to deduplicate changes to resources that don't actually change anything.
+    pub resource_fingerprints: HashMap<ComponentId, ChangeFingerprint>,
+}
+
+/// The system set in which the Bevy database gets synced with the Rerun database.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RerunSyncSet;
+
+/// The part of [`RerunSyncSet`] in which opt-in data (events, states…) gets logged.
+///
+/// Runs before the entities get synced, so that it can piggyback on full syncs.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct RerunLogSet;
+
+/// An additional point in the frame at which the Bevy database gets synced with the Rerun
+/// database, on top of the default one in `Last`.
+///
+/// Every sync point is indexed by its own sequence timeline, which counts how many times it ran,
+/// so that intermediate states within a single frame can be told apart (e.g. in between each
+/// `FixedUpdate` iteration).
+#[derive(Debug, Clone)]
+pub struct RerunSyncPoint {
+    /// Which schedule the sync runs in.
+    pub schedule: InternedScheduleLabel,
+
+    /// Which system set the sync runs in, if any.
+    pub set: Option<InternedSystemSet>,
+
+    /// The name of the sequence timeline that indexes this sync point.
+    pub timeline: String,
+}
+
+impl RerunSyncPoint {
+    #[inline]
+    pub fn new(schedule: impl ScheduleLabel, timeline: impl Into<String>) -> Self {
+        Self {
+            schedule: schedule.intern(),
+            set: None,
+            timeline: timeline.into(),
+        }
+    }
+
+    /// Runs the sync as part of `set`, within [`Self::schedule`].
+    #[inline]
+    pub fn in_set(mut self, set: impl SystemSet) -> Self {
+        self.set = Some(set.intern());
+        self
+    }
 }
 
 /// A plugin to sync the state of the Bevy database and the Rerun database.
-pub struct RerunSyncPlugin {
+///
+/// Only the entities matching `F` are synced.
+pub struct RerunSyncPlugin<F> {
     pub rec: rerun::RecordingStream,
+    pub tracking_mode: RerunTrackingMode,
+    pub sync_resources: bool,
+    pub timelines: RerunTimelines,
+    pub sync_points: Vec<RerunSyncPoint>,
+    pub filter: PhantomData<fn() -> F>,
 }
 
-impl Plugin for RerunSyncPlugin {
+impl<F: QueryFilter + 'static> Plugin for RerunSyncPlugin<F> {
     fn build(&self, app: &mut App) {
         self.rec
             .log_static("world", &rerun::ViewCoordinates::RIGHT_HAND_Y_UP)
             .ok_or_log_error();
+        // NOTE: Shared assets are instanced in world space.
+        self.rec
+            .log_static("assets", &rerun::ViewCoordinates::RIGHT_HAND_Y_UP)
+            .ok_or_log_error();
 
         let state = RerunSyncState {
             rec: self.rec.clone(),
             entities: Default::default(),
+            force_full_sync: false,
+            hierarchy_events: Default::default(),
+            removed_names: Default::default(),
+            tracking_mode: self.tracking_mode,
+            sync_resources: self.sync_resources,
+            timelines: self.timelines,
+            resources: Default::default(),
+            resource_fingerprints: Default::default(),
         };
 
+        if self.timelines.fixed_tick {
+            app.init_resource::<RerunFixedTick>()
+                .add_systems(FixedFirst, system_count_fixed_ticks);
+        }
+
         app.init_resource::<DefaultRerunComponentLoggers>()
+            .init_resource::<RerunReflectedComponentLoggers>()
+            .init_resource::<RerunChangeDedup>()
+            .init_resource::<RerunAssetsState>()
+            .init_resource::<RerunChangedAssets>()
             .insert_resource(state)
-            .add_systems(Last, system_sync_entities);
+            // NOTE: Make sure that everything that gets logged in `Last` agrees on the frame
+            // number.
+            .configure_sets(
+                Last,
+                (
+                    RerunSyncSet.before(bevy::core::update_frame_count),
+                    RerunLogSet.in_set(RerunSyncSet),
+                ),
+            )
+            .add_systems(
+                Last,
+                system_sync_entities::<F>
+                    .in_set(RerunSyncSet)
+                    .after(RerunLogSet),
+            );
+
+        // NOTE: The range of point lights is an overlay on top of the builtin logger, so that the
+        // user can replace or disable either one.
+        {
+            let world = app.world_mut();
+            let component_id = world.register_component::<PointLight>();
+            let mut loggers = world.get_resource_or_init::<RerunTypedComponentLoggers>();
+            let chain = loggers.entry(component_id).or_default();
+            if chain.builtin {
+                chain.push(RerunLogger::typed(bevy_point_light_range).with_suffix("light_range"));
+            }
+        }
+
+        track_asset_changes::<Image>(app);
+        track_asset_changes::<Mesh>(app);
+        track_asset_changes::<StandardMaterial>(app);
+        track_asset_changes::<ColorMaterial>(app);
+
+        // NOTE: Decoded textures must be evicted before they get logged again.
+        #[cfg(feature = "compressed_textures")]
+        app.init_resource::<crate::RerunCompressedTextures>()
+            .add_systems(
+                Last,
+                crate::compressed_textures::evict_compressed_textures
+                    .after(bevy::asset::AssetEvents)
+                    .before(RerunSyncSet),
+            );
+
+        for sync_point in &self.sync_points {
+            let system = system_sync_point::<F>(sync_point.timeline.clone()).in_set(RerunSyncSet);
+            if let Some(set) = sync_point.set {
+                app.add_systems(sync_point.schedule, system.in_set(set));
+            } else {
+                app.add_systems(sync_point.schedule, system);
+            }
+        }
     }
 }
 
 // ---
 
-// TODO(cmc): some multithreading parallel iterators for the whole sync wouldn't hurt.
+/// Returns an exclusive system that syncs entities, indexed by its own `timeline`.
+fn system_sync_point<F: QueryFilter>(timeline: String) -> impl FnMut(&mut World, Local<'_, u64>) {
+    move |world, mut runs| {
+        let rec = world.resource::<RerunSyncState>().rec.clone();
+
+        rec.set_time_sequence(timeline.as_str(), *runs as i64);
+        *runs += 1;
+
+        system_sync_entities::<F>(world);
+
+        // NOTE: Recording time is thread-local: don't let this timeline leak into whatever
+        // gets logged next on this thread.
+        rec.disable_timeline(timeline.as_str());
+    }
+}
 
-fn system_sync_entities(world: &mut World) {
+fn system_sync_entities<F: QueryFilter>(world: &mut World) {
     let _trace = info_span!("sync_entities").entered();
 
     let state = world.resource::<RerunSyncState>();
     let rec = state.rec.clone();
-
-    // TODO(cmc): we should be subscribing to hierarchy event in order to clear old entity paths as
-    // their hierarchy changes (and thus their path).
+    let force_full_sync = state.force_full_sync;
+    let tracking_mode = state.tracking_mode;
+    let sync_resources = state.sync_resources;
 
     let mut previous_entities = state.entities.clone();
     let mut current_entities = EntityHashMap::<rerun::EntityPath>::default();
     {
         set_recording_time(world, &rec);
-        sync_components(world, &mut current_entities, &mut previous_entities, &rec);
-        clear_despawned_entities(previous_entities, &rec);
+        let entity_ids = synced_entities::<F>(world, tracking_mode);
+        let moved_entities = sync_entity_paths(world, &entity_ids, &rec);
+        let changed_assets =
+            std::mem::take(&mut world.resource_mut::<RerunChangedAssets>().changed);
+        sync_assets(world, &entity_ids, &changed_assets, &rec, force_full_sync);
+        sync_components(
+            world,
+            entity_ids,
+            &mut current_entities,
+            &mut previous_entities,
+            &changed_assets,
+            &rec,
+            force_full_sync,
+            &moved_entities,
+        );
+        clear_unsynced_entities(world, previous_entities, &rec);
+
+        if sync_resources {
+            self::sync_resources(world, &rec, force_full_sync);
+        }
     }
 
     let mut state = world.resource_mut::<RerunSyncState>();
     state.entities = current_entities;
+    state.force_full_sync = false;
 }
 
 /// Synchronize Bevy's clock with the recording's clock.
-fn set_recording_time(world: &World, rec: &rerun::RecordingStream) {
+pub(crate) fn set_recording_time(world: &World, rec: &rerun::RecordingStream) {
     let _trace = info_span!("set_recording_time").entered();
 
-    let time = world.resource::<Time>();
-    let elapsed = time.elapsed_secs_f64();
+    let timelines = world.resource::<RerunSyncState>().timelines;
 
-    let tick = world.resource::<FrameCount>();
-    let frame = tick.0;
+    if timelines.sim_time {
+        let time = world.resource::<Time>();
+        rec.set_time_seconds("sim_time", time.elapsed_secs_f64());
+    }
 
-    rec.set_time_seconds("sim_time", elapsed);
-    // TODO(cmc): i'll log it once i can tell the blueprint to default to `sim_time`.
-    // rec.set_time_sequence("sim_frame", frame);
-    _ = frame;
+    if timelines.frame {
+        let tick = world.resource::<FrameCount>();
+        rec.set_time_sequence("frame", tick.0);
+    }
+
+    if timelines.fixed_tick {
+        if let Some(tick) = world.get_resource::<RerunFixedTick>() {
+            rec.set_time_sequence("fixed_tick", tick.0 as i64);
+        }
+    }
+
+    if timelines.virtual_time {
+        if let Some(time) = world.get_resource::<Time<Virtual>>() {
+            rec.set_time_seconds("virtual_time", time.elapsed_secs_f64());
+        }
+    }
+
+    if timelines.real_time {
+        if let Some(time) = world.get_resource::<Time<Real>>() {
+            rec.set_time_seconds("real_time", time.elapsed_secs_f64());
+        }
+    }
 }
 
-// TODO(cmc): implement proper subscription model for asset dependencies
-const DEPENDS_ON_IMAGES: &[&str] = &[
-    "bevy_render::mesh::components::Mesh3d",
-    "bevy_sprite::sprite::Sprite",
-];
-const DEPENDS_ON_MESHES: &[&str] = &[
-    "bevy_render::mesh::components::Mesh3d", //
-];
-const DEPENDS_ON_STDMATS: &[&str] = &[
-    "bevy_render::mesh::components::Mesh3d", //
-    "bevy_render::primitives::Aabb",         //
-];
-const DEPENDS_ON_COLMATS: &[&str] = &[
-    "bevy_render::mesh::components::Mesh3d", //
-    "bevy_render::primitives::Aabb",         //
-];
-
-/// Synchronize the Bevy and Rerun database by logging all components appropriately.
+/// Keeps the cached [`RerunEntityPath`]s of the synced `entity_ids` up to date as the hierarchy
+/// changes.
+///
+/// The old path of every entity that got moved around is recursively cleared.
+/// Returns all the entities whose path has changed: these need to be fully re-logged at their new
+/// path.
+fn sync_entity_paths(
+    world: &mut World,
+    entity_ids: &[Entity],
+    rec: &rerun::RecordingStream,
+) -> EntityHashSet {
+    let _trace = info_span!("sync_entity_paths").entered();
+
+    let synced_entities = entity_ids.iter().copied().collect::<EntityHashSet>();
+
+    // Anything that might have changed path: reparented entities, renamed entities, and new
+    // entities that don't have a cached path yet.
+    let mut dirty_entities = {
+        let mut cursor =
+            std::mem::take(&mut world.resource_mut::<RerunSyncState>().hierarchy_events);
+        let mut dirty_entities = world
+            .get_resource::<Events<HierarchyEvent>>()
+            .map(|events| {
+                cursor
+                    .read(events)
+                    .map(|event| match *event {
+                        HierarchyEvent::ChildAdded { child, .. }
+                        | HierarchyEvent::ChildRemoved { child, .. }
+                        | HierarchyEvent::ChildMoved { child, .. } => child,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        world.resource_mut::<RerunSyncState>().hierarchy_events = cursor;
+
+        // Entities that we already know aren't synced are skipped, until the hierarchy changes
+        // around them or they start being synced.
+        for &entity_id in &dirty_entities {
+            if let Ok(mut entity) = world.get_entity_mut(entity_id) {
+                entity.remove::<UnsyncedEntity>();
+            }
+        }
+        for &entity_id in entity_ids {
+            if world.get::<UnsyncedEntity>(entity_id).is_some() {
+                world.entity_mut(entity_id).remove::<UnsyncedEntity>();
+                dirty_entities.push(entity_id);
+            }
+        }
+
+        let mut renamed_or_new = world.query_filtered::<(Entity, Has<RerunEntityPath>), Or<(
+            (Without<RerunEntityPath>, Without<UnsyncedEntity>),
+            Changed<Name>,
+        )>>();
+        let mut unsynced_entities = Vec::new();
+        for (entity_id, has_entity_path) in renamed_or_new.iter(world) {
+            if synced_entities.contains(&entity_id) {
+                dirty_entities.push(entity_id);
+            } else if !has_entity_path {
+                unsynced_entities.push(entity_id);
+            }
+        }
+        for entity_id in unsynced_entities {
+            world.entity_mut(entity_id).insert(UnsyncedEntity);
+        }
+
+        let mut cursor = std::mem::take(&mut world.resource_mut::<RerunSyncState>().removed_names);
+        if let Some(events) = world
+            .component_id::<Name>()
+            .and_then(|name| world.removed_components().get(name))
+        {
+            dirty_entities.extend(cursor.read(events).cloned().map(Entity::from));
+        }
+        world.resource_mut::<RerunSyncState>().removed_names = cursor;
+
+        dirty_entities
+    };
+
+    let mut all_entities = world.query::<(Entity, Option<&Parent>, Option<&Name>)>();
+    all_entities.update_archetypes(world);
+
+    let mut visited = EntityHashSet::default();
+    let mut new_entity_paths = EntityHashMap::<rerun::EntityPath>::default();
+    while let Some(entity_id) = dirty_entities.pop() {
+        if !visited.insert(entity_id) {
+            continue;
+        }
+        let Ok(entity) = world.get_entity(entity_id) else {
+            continue; // despawned in the meantime
+        };
+
+        let entity_path = compute_entity_path(world, &all_entities, entity_id);
+
+        let old_entity_path = entity.get::<RerunEntityPath>();
+        if old_entity_path.map(|path| &path.0) == Some(&entity_path) {
+            continue;
+        }
+
+        if let Some(old_entity_path) = old_entity_path {
+            rec.log(old_entity_path.0.clone(), &rerun::Clear::recursive())
+                .ok_or_log_error();
+        }
+
+        // The path of an entity is a function of the path of its parent: the whole subtree moved.
+        if let Some(children) = entity.get::<Children>() {
+            dirty_entities.extend(children.iter().copied());
+        }
+
+        // NOTE: Entities that aren't synced are still walked through, as their descendants
+        // might be.
+        if synced_entities.contains(&entity_id) {
+            new_entity_paths.insert(entity_id, entity_path);
+        }
+    }
+
+    let mut moved_entities = EntityHashSet::default();
+    for (entity_id, entity_path) in new_entity_paths {
+        world
+            .entity_mut(entity_id)
+            .insert(RerunEntityPath(entity_path));
+        moved_entities.insert(entity_id);
+    }
+
+    moved_entities
+}
+
+/// Synchronize the Bevy and Rerun database by logging all components of `entity_ids`
+/// appropriately.
+///
+/// If `force_full_sync` is set, all components are logged, whether they changed or not.
+/// The same goes for all `moved_entities`, as well as the entities that weren't synced last time.
+///
+/// This happens in two phases:
+/// 1. A read-only parallel phase, where every entity's components get converted and logged on the
+///    [`ComputeTaskPool`].
+/// 2. A sequential phase, where the resulting bookkeeping gets written back into the world.
 //
 // TODO(cmc): obviously, iterating the world (literally, btw) is not a viable strategy.
+#[allow(clippy::too_many_arguments)]
 fn sync_components(
     world: &mut World,
+    entity_ids: Vec<Entity>,
     current_entities: &mut EntityHashMap<rerun::EntityPath>,
     previous_entities: &mut EntityHashMap<rerun::EntityPath>,
+    changed_assets: &HashSet<UntypedAssetId>,
     rec: &rerun::RecordingStream,
+    force_full_sync: bool,
+    moved_entities: &EntityHashSet,
 ) {
     let now = std::time::Instant::now();
 
@@ -124,152 +461,452 @@ fn sync_components(
     let mut all_entities = world.query::<(Entity, Option<&Parent>, Option<&Name>)>();
     all_entities.update_archetypes(world);
 
-    // TODO(cmc): do this the smart way
-    fn collect_events<A: Asset>(world: &mut World) -> Vec<AssetEvent<A>> {
-        let events = world.resource_mut::<Events<AssetEvent<A>>>();
-        let mut cursor = EventCursor::<AssetEvent<A>>::default();
-        cursor.read(&events).copied().collect()
+    world.resource_scope(
+        |world, mut reflected_loggers: Mut<'_, RerunReflectedComponentLoggers>| {
+            reflected_loggers.update(world);
+        },
+    );
+
+    let outputs = {
+        let ctx = SyncContext {
+            world,
+            all_entities: &all_entities,
+            typed_loggers: world.get_resource::<RerunTypedComponentLoggers>(),
+            loggers: world.get_resource::<RerunComponentLoggers>(),
+            default_loggers: world.resource::<DefaultRerunComponentLoggers>(),
+            reflected_loggers: world.resource::<RerunReflectedComponentLoggers>(),
+            change_dedup: world.resource::<RerunChangeDedup>(),
+            changed_assets,
+            force_full_sync,
+            moved_entities,
+            previous_entities,
+            rec,
+            timepoint: rec.now(),
+        };
+
+        let pool = ComputeTaskPool::get_or_init(TaskPool::default);
+        let batch_size = entity_ids
+            .len()
+            .div_ceil(pool.thread_num().max(1) * 4)
+            .max(1);
+
+        let ctx = &ctx;
+        pool.scope(|scope| {
+            for batch in entity_ids.chunks(batch_size) {
+                scope.spawn(async move {
+                    let _trace = info_span!("sync_components_batch").entered();
+
+                    with_timepoint(ctx.rec, &ctx.timepoint, || {
+                        batch
+                            .iter()
+                            .map(|&entity_id| sync_entity_components(ctx, entity_id))
+                            .collect::<Vec<_>>()
+                    })
+                });
+            }
+        })
+    };
+
+    {
+        let _trace = info_span!("sync_components_write_back").entered();
+
+        for output in outputs.into_iter().flatten() {
+            let EntitySyncOutput {
+                entity_id,
+                entity_path,
+                fingerprints,
+                components,
+                removed_components,
+            } = output;
+
+            current_entities.insert(entity_id, entity_path);
+            previous_entities.remove(&entity_id);
+
+            let mut entity = world.entity_mut(entity_id);
+
+            if !fingerprints.is_empty() || !removed_components.is_empty() {
+                if let Some(mut last_fingerprints) = entity.get_mut::<CurrentFingerprints>() {
+                    for component_id in &removed_components {
+                        last_fingerprints.remove(component_id);
+                    }
+                    last_fingerprints.extend(fingerprints);
+                } else {
+                    entity.insert(CurrentFingerprints(fingerprints));
+                }
+            }
+
+            if !components.is_empty() || !removed_components.is_empty() {
+                if let Some(mut last_components) = entity.get_mut::<CurrentComponents>() {
+                    for component_id in &removed_components {
+                        last_components.remove(component_id);
+                    }
+                    last_components.extend(components);
+                } else {
+                    entity.insert(CurrentComponents(components));
+                }
+            }
+        }
     }
-    let image_events = collect_events::<Image>(world);
-    let mesh_events = collect_events::<Mesh>(world);
-    let stdmat_events = collect_events::<StandardMaterial>(world);
-    let colmat_events = collect_events::<ColorMaterial>(world);
 
-    // TODO(cmc): no good reason to clone this every time
-    let loggers = world.get_resource::<RerunComponentLoggers>().cloned();
-    let default_loggers = world.resource::<DefaultRerunComponentLoggers>().clone();
+    trace!(elapsed=?now.elapsed(), "component sync done");
+}
+
+/// Runs `f` with the recording time of the calling thread set to `timepoint`, then restores
+/// whatever time that thread had before.
+///
+/// Recording time is thread-local: the workers of the parallel phase are shared with the rest of
+/// the app (and the calling thread might run some of the tasks itself), so they must not be left
+/// with the time of the sync.
+fn with_timepoint<R>(
+    rec: &rerun::RecordingStream,
+    timepoint: &rerun::TimePoint,
+    f: impl FnOnce() -> R,
+) -> R {
+    let previous = rec.now();
 
-    let mut deferred_hash_updates = Vec::new();
+    rec.reset_time();
+    rec.set_timepoint(timepoint.clone());
 
-    let mut entities = world.query::<Entity>();
-    for entity_id in entities.iter(world) {
-        // TODO(cmc): should cache this and deal with `HierarchyEvent` accordingly.
-        let entity_path = compute_entity_path(world, &all_entities, entity_id);
+    let result = f();
+
+    rec.reset_time();
+    rec.set_timepoint(previous);
+
+    result
+}
+
+/// Everything that's shared by all the workers of the parallel phase of [`sync_components`].
+struct SyncContext<'w> {
+    world: &'w World,
+    all_entities: &'w QueryState<(Entity, Option<&'w Parent>, Option<&'w Name>)>,
+    typed_loggers: Option<&'w RerunTypedComponentLoggers>,
+    loggers: Option<&'w RerunComponentLoggers>,
+    default_loggers: &'w DefaultRerunComponentLoggers,
+    reflected_loggers: &'w RerunReflectedComponentLoggers,
+    change_dedup: &'w RerunChangeDedup,
+    changed_assets: &'w HashSet<UntypedAssetId>,
+    force_full_sync: bool,
+    moved_entities: &'w EntityHashSet,
+    previous_entities: &'w EntityHashMap<rerun::EntityPath>,
+    rec: &'w rerun::RecordingStream,
+    timepoint: rerun::TimePoint,
+}
 
-        current_entities.insert(entity_id, entity_path.clone());
-        previous_entities.remove(&entity_id);
+/// What the parallel phase of [`sync_components`] wants written back into the world, for a single
+/// entity.
+struct EntitySyncOutput {
+    entity_id: Entity,
+    entity_path: rerun::EntityPath,
 
-        let entity = world.entity(entity_id);
+    /// The fingerprints of all the components that were logged.
+    fingerprints: HashMap<ComponentId, ChangeFingerprint>,
 
-        let change_tick = world.read_change_tick();
-        let last_change_tick = world.last_change_tick();
+    /// Where the data of all the components that were logged ended up.
+    components: HashMap<ComponentId, Vec<(rerun::ComponentDescriptor, rerun::EntityPath)>>,
 
-        let mut current_hashes = CurrentHashes::default();
-        let empty_hashes = CurrentHashes::default();
-        let last_hashes = world
-            .entity(entity_id)
-            .get::<CurrentHashes>()
-            .unwrap_or(&empty_hashes);
+    /// The components that were removed since the last sync, and have been cleared accordingly.
+    removed_components: Vec<ComponentId>,
+}
 
-        let mut as_components: HashMap<Option<&'static str>, Vec<Box<dyn rerun::AsComponents>>> =
-            Default::default();
-        for component in world.inspect_entity(entity_id) {
-            let mut has_changed = entity
-                .get_change_ticks_by_id(component.id())
-                .map_or(false, |changes| {
-                    changes.is_changed(last_change_tick, change_tick)
+/// Converts and logs all the components of a single entity.
+///
+/// Read-only: this is safe to run in parallel for different entities.
+fn sync_entity_components(ctx: &SyncContext<'_>, entity_id: Entity) -> EntitySyncOutput {
+    let SyncContext {
+        world,
+        all_entities,
+        typed_loggers,
+        loggers,
+        default_loggers,
+        reflected_loggers,
+        change_dedup,
+        changed_assets,
+        force_full_sync,
+        moved_entities,
+        previous_entities,
+        rec,
+        timepoint: _,
+    } = ctx;
+
+    let entity_path = cached_entity_path(world, all_entities, entity_id);
+    let entity = world.entity(entity_id);
+
+    let change_tick = world.read_change_tick();
+    let last_change_tick = world.last_change_tick();
+
+    let empty_components = CurrentComponents::default();
+    let last_components = entity
+        .get::<CurrentComponents>()
+        .unwrap_or(&empty_components);
+
+    // NOTE: Clears are coarse: they wipe all the data at a given entity path, not just the data
+    // from the component that was removed. Everything that's left must then be re-logged.
+    let removed_components = last_components
+        .keys()
+        .filter(|component_id| !entity.contains_id(**component_id))
+        .copied()
+        .collect::<Vec<_>>();
+    {
+        let mut cleared_entity_paths = std::collections::BTreeSet::new();
+        for component_id in &removed_components {
+            for (_, entity_path) in &last_components[component_id] {
+                if cleared_entity_paths.insert(entity_path) {
+                    rec.log(entity_path.clone(), &rerun::Clear::flat())
+                        .ok_or_log_error();
+                }
+            }
+        }
+    }
+
+    let force_full_sync = *force_full_sync
+        || moved_entities.contains(&entity_id)
+        || !previous_entities.contains_key(&entity_id)
+        || !removed_components.is_empty();
+
+    let mut current_fingerprints = HashMap::<ComponentId, ChangeFingerprint>::default();
+    let last_fingerprints = entity.get::<CurrentFingerprints>();
+
+    let mut current_components = HashMap::default();
+
+    let mut as_components: HashMap<Option<&'static str>, Vec<Box<dyn rerun::AsComponents>>> =
+        Default::default();
+    for component in world.inspect_entity(entity_id) {
+        let change_ticks = entity.get_change_ticks_by_id(component.id());
+        let is_added = change_ticks.map_or(false, |changes| {
+            changes.is_added(last_change_tick, change_tick)
+        });
+        let is_changed = change_ticks.map_or(false, |changes| {
+            changes.is_changed(last_change_tick, change_tick)
+        });
+
+        let component_loggers = || {
+            get_component_loggers(
+                component,
+                *typed_loggers,
+                *loggers,
+                default_loggers,
+                reflected_loggers,
+            )
+        };
+
+        // NOTE: Loggers are only looked up ahead of time if there's any chance that one of their
+        // assets changed.
+        let mut resolved_loggers = None;
+        let depends_on_changed_assets = !changed_assets.is_empty()
+            && resolved_loggers
+                .get_or_insert_with(|| component_loggers().collect::<Vec<_>>())
+                .iter()
+                .any(|logger| {
+                    logger
+                        .asset_dependencies(world, entity)
+                        .iter()
+                        .any(|id| changed_assets.contains(id))
                 });
 
-            // TODO(cmc): implement proper subscription model for asset dependencies
-            has_changed |=
-                !image_events.is_empty() && DEPENDS_ON_IMAGES.contains(&component.name());
-            has_changed |= !mesh_events.is_empty() && DEPENDS_ON_MESHES.contains(&component.name());
-            has_changed |=
-                !stdmat_events.is_empty() && DEPENDS_ON_STDMATS.contains(&component.name());
-            has_changed |=
-                !colmat_events.is_empty() && DEPENDS_ON_COLMATS.contains(&component.name());
+        if !(force_full_sync || is_changed || depends_on_changed_assets) {
+            continue;
+        }
 
-            if !has_changed {
+        {
+            // NOTE: The component itself might be unchanged if it's one of its assets that
+            // changed, and a newly added component must always be logged, regardless of what
+            // was there before.
+            let must_log = force_full_sync || is_added || depends_on_changed_assets;
+
+            let dedup = change_dedup.get(component);
+            let ptr = entity.get_by_id(component.id()).ok();
+            let is_duplicate = !must_log
+                && ptr
+                    .zip(last_fingerprints.and_then(|last| last.get(&component.id())))
+                    .is_some_and(|(ptr, last)| dedup.is_unchanged(world, component, ptr, last));
+            if is_duplicate {
                 continue;
             }
 
-            {
-                // NOTE: Default the hash to 0, that way `<missing reflection data>` will be mapped
-                // to 0 and will be logged only once rather than every frame.
-                let component_hash = component_to_hash(world, entity, component).unwrap_or(0u64);
-                current_hashes.insert(component.id(), component_hash);
-                if last_hashes.get(&component.id()) == Some(&component_hash) {
-                    continue;
-                }
-            }
+            let fingerprint = ptr.and_then(|ptr| dedup.fingerprint(world, component, ptr));
 
-            if let Some(logger) =
-                get_component_logger(component, loggers.as_ref(), &default_loggers)
-            {
-                let (suffix, data) = logger(world, &all_entities, entity, component);
-                as_components.entry(suffix).or_default().extend(data);
+            if let Some(fingerprint) = fingerprint {
+                current_fingerprints.insert(component.id(), fingerprint);
             }
         }
 
-        if !current_hashes.is_empty() {
-            deferred_hash_updates.push((entity_id, current_hashes));
-        }
+        for logger in resolved_loggers.unwrap_or_else(|| component_loggers().collect()) {
+            let (suffix, data) = logger(world, all_entities, entity, component);
 
-        let mut current_components = HashMap::default();
+            let component_entity_path = suffixed_entity_path(&entity_path, suffix);
+            let descriptors = data
+                .iter()
+                .flat_map(|data| data.as_component_batches())
+                .map(|batch| {
+                    (
+                        rerun::ComponentBatch::descriptor(&batch).into_owned(),
+                        component_entity_path.clone(),
+                    )
+                });
+            current_components
+                .entry(component.id())
+                .or_insert_with(Vec::new)
+                .extend(descriptors);
 
-        // TODO(cmc): lots of inneficiencies and awkward collections that are forced upon us
-        // because of how the RecordingStream API is designed.
-        // After quite a bit of juggling it's not too bad though.
-        for (suffix, as_components) in as_components {
-            let component_batches = as_components
+            as_components.entry(suffix).or_default().extend(data);
+        }
+    }
+
+    // TODO(cmc): lots of inneficiencies and awkward collections that are forced upon us
+    // because of how the RecordingStream API is designed.
+    // After quite a bit of juggling it's not too bad though.
+    for (suffix, as_components) in as_components {
+        let component_batches = as_components
+            .iter()
+            .map(|data| data.as_component_batches())
+            .collect::<Vec<_>>();
+
+        rec.log_component_batches(
+            suffixed_entity_path(&entity_path, suffix),
+            false,
+            component_batches
                 .iter()
-                .map(|data| data.as_component_batches())
-                .collect::<Vec<_>>();
-
-            let entity_path: rerun::EntityPath = suffix.map_or_else(
-                || entity_path.clone(),
-                // NOTE(cmc): The extra `comps/` is crucial so that we can easily clear everything
-                // (we need a recursive clear but not really)
-                |suffix| entity_path.join(&"comps".into()).join(&suffix.into()),
-            );
+                .flatten()
+                .map(|batch| batch as &dyn rerun::ComponentBatch),
+        )
+        .ok_or_log_error();
+    }
 
-            for batches in &component_batches {
-                for batch in batches {
-                    current_components.insert(
-                        rerun::ComponentBatch::descriptor(batch).into_owned(),
-                        entity_path.clone(),
-                    );
-                }
-            }
+    EntitySyncOutput {
+        entity_id,
+        entity_path,
+        fingerprints: current_fingerprints,
+        components: current_components,
+        removed_components,
+    }
+}
 
-            rec.log_component_batches(
-                entity_path,
-                false,
-                component_batches
-                    .iter()
-                    .flatten()
-                    .map(|batch| batch as &dyn rerun::ComponentBatch),
-            )
-            .ok_or_log_error();
+fn suffixed_entity_path(
+    entity_path: &rerun::EntityPath,
+    suffix: Option<&'static str>,
+) -> rerun::EntityPath {
+    suffix.map_or_else(
+        || entity_path.clone(),
+        // NOTE(cmc): The extra `comps/` is crucial so that we can easily clear everything
+        // (we need a recursive clear but not really)
+        |suffix| entity_path.join(&"comps".into()).join(&suffix.into()),
+    )
+}
+
+/// Synchronize all resources, each under `resources/<type_name>`.
+///
+/// If `force_full_sync` is set, all resources are logged, whether they changed or not.
+fn sync_resources(world: &mut World, rec: &rerun::RecordingStream, force_full_sync: bool) {
+    let _trace = info_span!("sync_resources").entered();
+
+    let change_tick = world.read_change_tick();
+    let last_change_tick = world.last_change_tick();
+
+    let loggers = world.get_resource::<RerunComponentLoggers>();
+    let default_loggers = world.resource::<DefaultRerunComponentLoggers>();
+    let type_registry = world.resource::<AppTypeRegistry>();
+    let change_dedup = world.resource::<RerunChangeDedup>();
+    let state = world.resource::<RerunSyncState>();
+
+    let mut current_resources = HashMap::default();
+    let mut current_fingerprints = HashMap::default();
+
+    for (resource, ptr) in world.iter_resources() {
+        // NOTE: Revy's own bookkeeping is of no interest to anyone.
+        if resource.name().starts_with("revy::") {
+            continue;
         }
 
-        let empty_components = CurrentComponents::default();
-        let last_components = entity
-            .get::<CurrentComponents>()
-            .unwrap_or(&empty_components);
+        let entity_path = resource_entity_path(resource);
+        current_resources.insert(resource.id(), entity_path.clone());
 
-        for (component_desc, entity_path) in last_components.iter() {
-            if !current_components.contains_key(component_desc) {
-                rec.log(entity_path.clone(), &rerun::Clear::flat())
-                    .ok_or_log_error();
+        let change_ticks = world.get_resource_change_ticks_by_id(resource.id());
+        let is_added = change_ticks.map_or(false, |changes| {
+            changes.is_added(last_change_tick, change_tick)
+        });
+        let is_changed = change_ticks.map_or(false, |changes| {
+            changes.is_changed(last_change_tick, change_tick)
+        });
+
+        if !(force_full_sync || is_changed) {
+            continue;
+        }
+
+        {
+            let must_log = force_full_sync || is_added;
+
+            let dedup = change_dedup.get(resource);
+            let is_duplicate = !must_log
+                && state
+                    .resource_fingerprints
+                    .get(&resource.id())
+                    .is_some_and(|last| dedup.is_unchanged(world, resource, ptr, last));
+            if is_duplicate {
+                continue;
+            }
+
+            let fingerprint = dedup.fingerprint(world, resource, ptr);
+
+            if let Some(fingerprint) = fingerprint {
+                current_fingerprints.insert(resource.id(), fingerprint);
+            }
+        }
+
+        if let Some(logger) = get_resource_logger(resource, loggers, default_loggers, type_registry)
+        {
+            let (suffix, data) = logger.log_resource(world, resource);
+            if let Some(data) = data {
+                rec.log_component_batches(
+                    suffixed_entity_path(&entity_path, suffix),
+                    false,
+                    data.as_component_batches()
+                        .iter()
+                        .map(|batch| batch as &dyn rerun::ComponentBatch),
+                )
+                .ok_or_log_error();
             }
         }
     }
 
-    for (entity_id, hashes) in deferred_hash_updates {
-        world.entity_mut(entity_id).insert(hashes);
+    for (resource_id, entity_path) in &state.resources {
+        if !current_resources.contains_key(resource_id) {
+            rec.log(entity_path.clone(), &rerun::Clear::recursive())
+                .ok_or_log_error();
+        }
     }
 
-    trace!(elapsed=?now.elapsed(), "component sync done");
+    let mut state = world.resource_mut::<RerunSyncState>();
+    state
+        .resource_fingerprints
+        .retain(|resource_id, _| current_resources.contains_key(resource_id));
+    state.resource_fingerprints.extend(current_fingerprints);
+    state.resources = current_resources;
+}
+
+fn resource_entity_path(resource: &ComponentInfo) -> rerun::EntityPath {
+    rerun::EntityPath::new(vec![
+        rerun::EntityPathPart::new("resources"),
+        rerun::EntityPathPart::new(resource.name()),
+    ])
 }
 
-fn clear_despawned_entities(
+/// Clears all the entities that were synced last time but aren't anymore, either because they
+/// were despawned or because they don't pass the filters anymore.
+///
+/// The bookkeeping of the latter is removed too, so that they get fully re-logged, at their
+/// up-to-date path, if they ever come back.
+fn clear_unsynced_entities(
+    world: &mut World,
     previous_entities: EntityHashMap<rerun::EntityPath>,
     rec: &rerun::RecordingStream,
 ) {
-    let _trace = info_span!("clear_despawned_entities").entered();
+    let _trace = info_span!("clear_unsynced_entities").entered();
+
+    for (entity_id, entity_path) in previous_entities {
+        if let Ok(mut entity) = world.get_entity_mut(entity_id) {
+            entity.remove::<(CurrentFingerprints, CurrentComponents, RerunEntityPath)>();
+        }
 
-    for (_entity, entity_path) in previous_entities {
         rec.log(
             entity_path.join(&"comps".into()),
             &rerun::Clear::recursive(),
@@ -283,47 +920,24 @@ fn clear_despawned_entities(
 
 // ---
 
-fn component_to_hash(
-    world: &World,
-    entity: EntityRef<'_>,
-    component: &ComponentInfo,
-) -> Option<u64> {
-    let type_registry = world.resource::<AppTypeRegistry>();
-    let type_registry = type_registry.read();
-
-    component
-        .type_id()
-        .and_then(|tid| type_registry.get(tid))
-        .and_then(|ty| ty.data::<ReflectFromPtr>())
-        .and_then(|reflect_from_ptr| {
-            #[allow(unsafe_code)]
-            let reflected = entity
-                .get_by_id(component.id())
-                // Safety: the type registry cannot be wrong, surely
-                .map(|ptr| unsafe { reflect_from_ptr.as_reflect(ptr) });
-
-            // TODO(cmc): `Reflect::reflect_hash` is basically never available so we go the long way
-            // instead... this is likely waaay too costly in practice :)
-            reflected.ok().and_then(|reflected| {
-                let serializer =
-                    ReflectSerializer::new(reflected.as_partial_reflect(), &type_registry);
-                let mut bytes = Vec::<u8>::new();
-                ron::ser::to_writer(&mut bytes, &serializer).ok()?;
-
-                use std::hash::Hasher;
-                let mut hasher = AHasher::default();
-                bytes.hash(&mut hasher);
-                Some(hasher.finish())
-            })
-        })
-}
-
 /// Used to deduplicate changes to components that don't actually change anything.
+///
+/// See [`crate::ChangeDedup`].
 //
 // TODO(cmc): we desperately need to be able to filter noise in the timeline panel.
+#[derive(Component, Debug, Default, Deref, DerefMut)]
+struct CurrentFingerprints(HashMap<ComponentId, ChangeFingerprint>);
+
+/// Marks the entities that were found not to be synced, so that [`sync_entity_paths`] doesn't
+/// have to look at them again every frame.
+///
+/// Removed as soon as the hierarchy changes around them or they start being synced.
+#[derive(Component, Debug, Default)]
+struct UnsyncedEntity;
+
+/// Keeps track of where the data of all components of an entity was logged, in order to `Clear`
+/// removed ones.
 #[derive(Component, Debug, Clone, Default, Deref, DerefMut)]
-struct CurrentHashes(HashMap<ComponentId, u64>);
-
-/// Keeps track of all components on an entity in order to `Clear` removed ones.
-#[derive(Component, Debug, Clone, Default, Deref, DerefMut)]
-struct CurrentComponents(HashMap<rerun::ComponentDescriptor, rerun::EntityPath>);
+struct CurrentComponents(
+    HashMap<ComponentId, Vec<(rerun::ComponentDescriptor, rerun::EntityPath)>>,
+);
@@ -0,0 +1,355 @@
+//! Headless utilities to test what Revy logs, without ever spawning a Rerun Viewer.
+//!
+//! Requires the `testing` feature, typically enabled from your `[dev-dependencies]`.
+//!
+//! ```rust,ignore
+//! let mut app = revy::testing::RerunTestApp::new();
+//! let player = app.app.world_mut().spawn((Name::new("player"), Transform::default())).id();
+//! app.step(2);
+//!
+//! let capture = app.capture();
+//! let entity_path = format!("world/{player:?}_player");
+//! assert!(capture.latest(&entity_path, "rerun.components.Translation3D").is_some());
+//! capture.assert_snapshot("tests/snapshots/player.txt");
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+    time::Duration,
+};
+
+use bevy::{ecs::query::QueryFilter, prelude::*, time::TimeUpdateStrategy};
+use rerun::external::arrow2::{array::Array, datatypes::DataType};
+
+use crate::{replay::RerunRecording, RerunPlugin};
+
+// ---
+
+/// Set this environment variable to `1` to create or overwrite snapshots rather than compare
+/// against them.
+pub const UPDATE_SNAPSHOTS_ENV_VAR: &str = "REVY_UPDATE_SNAPSHOTS";
+
+/// A headless [`App`] that runs [`RerunPlugin`] against an in-memory recording.
+///
+/// The app runs with [`MinimalPlugins`] and a fixed 60Hz frame delta, so its output is
+/// deterministic. Resource syncing is disabled by default, since engine resources are rarely
+/// deterministic.
+pub struct RerunTestApp {
+    pub app: App,
+
+    rec: rerun::RecordingStream,
+    storage: rerun::sink::MemorySinkStorage,
+    recording: RerunRecording,
+
+    /// Everything that was logged so far, as-is.
+    msgs: Vec<rerun::log::LogMsg>,
+}
+
+impl Default for RerunTestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RerunTestApp {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_plugin(RerunPlugin::new)
+    }
+
+    /// Uses the [`RerunPlugin`] returned by `f`, which is passed the in-memory recording.
+    ///
+    /// The `frame` timeline is always enabled: that's what the captured data is indexed by.
+    pub fn with_plugin<F: QueryFilter + 'static>(
+        f: impl FnOnce(rerun::RecordingStream) -> RerunPlugin<F>,
+    ) -> Self {
+        #[allow(clippy::unwrap_used)] // can only fail if the SDK fails to spawn its batcher thread
+        let (rec, storage) = rerun::RecordingStreamBuilder::new("revy_test")
+            .memory()
+            .unwrap();
+
+        let mut plugin = f(rec.clone());
+        plugin.timelines.frame = true;
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            bevy::hierarchy::HierarchyPlugin,
+            TransformPlugin,
+        ))
+        // NOTE: Revy keeps track of changes to those.
+        .init_asset::<Image>()
+        .init_asset::<Mesh>()
+        .init_asset::<StandardMaterial>()
+        .init_asset::<ColorMaterial>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .add_plugins(plugin);
+
+        Self {
+            app,
+            rec,
+            storage,
+            recording: RerunRecording::default(),
+            msgs: Vec::new(),
+        }
+    }
+
+    /// Runs `frames` updates of the app.
+    pub fn step(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            self.app.update();
+        }
+        self
+    }
+
+    /// Returns everything that's been logged so far.
+    pub fn capture(&mut self) -> RerunCapture {
+        self.rec.flush_blocking();
+        for msg in self.storage.take() {
+            self.msgs.push(msg.clone());
+            if let Err(err) = self.recording.insert(msg) {
+                error!(%err, "failed to decode captured data");
+            }
+        }
+
+        RerunCapture::new(&self.recording)
+    }
+
+    /// Writes everything that's been logged so far to an `.rrd` file, e.g. to replay it with
+    /// [`crate::RerunReplayPlugin`].
+    pub fn save(&mut self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        self.capture();
+
+        let bytes = rerun::external::re_log_encoding::encoder::encode_ref_as_bytes_local(
+            self.msgs.iter().map(Ok),
+        )
+        .map_err(std::io::Error::other)?;
+        std::fs::write(path, bytes)
+    }
+}
+
+// ---
+
+/// The values of a component, per frame.
+///
+/// Static data is indexed by `None`. Each frame may hold several values, in logging order.
+pub type RerunCapturedValues = BTreeMap<Option<i64>, Vec<String>>;
+
+/// Everything that was logged to a [`RerunTestApp`], indexed by entity path, component and frame.
+///
+/// Text data is stored as-is, everything else is stored as its debug representation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RerunCapture {
+    entities: BTreeMap<String, BTreeMap<String, RerunCapturedValues>>,
+
+    /// The raw times at which data was logged, per entity path and timeline.
+    times: BTreeMap<String, BTreeMap<String, BTreeSet<i64>>>,
+}
+
+impl RerunCapture {
+    fn new(recording: &RerunRecording) -> Self {
+        let timeline = rerun::Timeline::new_sequence("frame");
+
+        let mut entities: BTreeMap<String, BTreeMap<String, RerunCapturedValues>> = BTreeMap::new();
+        let mut times: BTreeMap<String, BTreeMap<String, BTreeSet<i64>>> = BTreeMap::new();
+        for chunk in recording.chunks() {
+            let entity_path = normalize_entity_path(&chunk.entity_path().to_string());
+
+            let entity_times = times.entry(entity_path.clone()).or_default();
+            for (timeline, column) in chunk.timelines() {
+                entity_times
+                    .entry(timeline.name().to_string())
+                    .or_default()
+                    .extend(column.times_raw());
+            }
+
+            let indices = chunk.iter_indices(&timeline).collect::<Vec<_>>();
+            let entity = entities.entry(entity_path).or_default();
+
+            for (component_name, per_desc) in chunk.components().iter() {
+                let Some(list_array) = per_desc.values().next() else {
+                    continue;
+                };
+
+                let values = entity.entry(component_name.to_string()).or_default();
+                for (row, (time, _)) in indices.iter().enumerate() {
+                    if !list_array.is_valid(row) {
+                        continue;
+                    }
+
+                    let frame = (!time.is_static()).then(|| time.as_i64());
+                    values
+                        .entry(frame)
+                        .or_default()
+                        .push(format_cell(&*list_array.value(row)));
+                }
+            }
+        }
+
+        Self { entities, times }
+    }
+
+    /// Loads everything that was written to an `.rrd` file, e.g. a bug report.
+    pub fn from_rrd_filepath(path: impl AsRef<Path>) -> rerun::external::anyhow::Result<Self> {
+        RerunRecording::from_rrd_filepath(path.as_ref()).map(|recording| Self::new(&recording))
+    }
+
+    /// All the entity paths that data was logged to.
+    pub fn entity_paths(&self) -> impl Iterator<Item = &str> {
+        self.entities.keys().map(String::as_str)
+    }
+
+    /// All the components that were logged at `entity_path`.
+    pub fn components(&self, entity_path: &str) -> impl Iterator<Item = &str> {
+        self.entities
+            .get(&normalize_entity_path(entity_path))
+            .into_iter()
+            .flat_map(|components| components.keys().map(String::as_str))
+    }
+
+    /// All the timelines that data at `entity_path` was indexed by.
+    pub fn timelines(&self, entity_path: &str) -> impl Iterator<Item = &str> {
+        self.times
+            .get(&normalize_entity_path(entity_path))
+            .into_iter()
+            .flat_map(|timelines| timelines.keys().map(String::as_str))
+    }
+
+    /// All the raw times at which data was logged at `entity_path`, on `timeline`, in order.
+    ///
+    /// Sequence timelines yield sequence numbers, temporal ones yield nanoseconds.
+    pub fn times(&self, entity_path: &str, timeline: &str) -> impl Iterator<Item = i64> + '_ {
+        self.times
+            .get(&normalize_entity_path(entity_path))
+            .and_then(|timelines| timelines.get(timeline))
+            .into_iter()
+            .flatten()
+            .copied()
+    }
+
+    /// All the values of `component` that were logged at `entity_path`.
+    pub fn values(&self, entity_path: &str, component: &str) -> Option<&RerunCapturedValues> {
+        self.entities
+            .get(&normalize_entity_path(entity_path))
+            .and_then(|components| components.get(component))
+    }
+
+    /// The last value of `component` that was logged at `entity_path`.
+    pub fn latest(&self, entity_path: &str, component: &str) -> Option<&str> {
+        self.values(entity_path, component)
+            .and_then(|values| values.values().next_back())
+            .and_then(|values| values.last())
+            .map(String::as_str)
+    }
+
+    /// Only keeps the `(entity_path, component)` pairs for which `f` returns `true`.
+    ///
+    /// Useful to leave out non-deterministic data (e.g. hashes) from snapshots.
+    pub fn filtered(mut self, mut f: impl FnMut(&str, &str) -> bool) -> Self {
+        self.entities.retain(|entity_path, components| {
+            components.retain(|component, _| f(entity_path, component));
+            !components.is_empty()
+        });
+        self
+    }
+
+    /// A stable, human-readable text representation of the capture, for snapshot testing.
+    pub fn to_snapshot(&self) -> String {
+        use std::fmt::Write as _;
+
+        let mut snapshot = String::new();
+        for (entity_path, components) in &self.entities {
+            _ = writeln!(snapshot, "{entity_path}");
+            for (component, values) in components {
+                _ = writeln!(snapshot, "  {component}");
+                for (frame, values) in values {
+                    let frame = frame.map_or_else(|| "static".to_owned(), |f| f.to_string());
+                    for value in values {
+                        let value = value.replace('\n', "\n      ");
+                        _ = writeln!(snapshot, "    [{frame}] {value}");
+                    }
+                }
+            }
+        }
+
+        snapshot
+    }
+
+    /// Compares the capture against the snapshot stored at `path`, and panics if they differ.
+    ///
+    /// The snapshot is (over)written instead if the [`UPDATE_SNAPSHOTS_ENV_VAR`] environment
+    /// variable is set. A missing snapshot is a failure otherwise, so that CI can't silently
+    /// accept whatever got logged.
+    #[track_caller]
+    pub fn assert_snapshot(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let snapshot = self.to_snapshot();
+
+        let update = std::env::var_os(UPDATE_SNAPSHOTS_ENV_VAR).is_some_and(|value| value == "1");
+        if update {
+            if let Some(dir) = path.parent() {
+                _ = std::fs::create_dir_all(dir);
+            }
+            if let Err(err) = std::fs::write(path, &snapshot) {
+                panic!("failed to write snapshot {path:?}: {err}");
+            }
+            return;
+        }
+
+        let expected = match std::fs::read_to_string(path) {
+            Ok(expected) => expected,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                panic!("missing snapshot {path:?}: set {UPDATE_SNAPSHOTS_ENV_VAR}=1 to create it")
+            }
+            Err(err) => panic!("failed to read snapshot {path:?}: {err}"),
+        };
+
+        if expected != snapshot {
+            let num_lines = usize::max(expected.lines().count(), snapshot.lines().count());
+            let Some((line, (expected_line, actual_line))) = expected
+                .lines()
+                .chain(std::iter::repeat(""))
+                .zip(snapshot.lines().chain(std::iter::repeat("")))
+                .take(num_lines)
+                .enumerate()
+                .find(|(_, (expected, actual))| expected != actual)
+            else {
+                panic!(
+                    "snapshot {path:?} doesn't match: all lines are the same, but line endings or \
+                     trailing newlines differ\n\n\
+                     set {UPDATE_SNAPSHOTS_ENV_VAR}=1 to update it"
+                );
+            };
+            panic!(
+                "snapshot {path:?} doesn't match (first difference on line {}):\n\
+                 expected: {expected_line}\n  actual: {actual_line}\n\n\
+                 set {UPDATE_SNAPSHOTS_ENV_VAR}=1 to update it",
+                line + 1
+            );
+        }
+    }
+}
+
+/// Makes sure entity paths always look the same, whether they were written with a leading slash
+/// or not.
+fn normalize_entity_path(entity_path: &str) -> String {
+    rerun::EntityPath::parse_forgiving(entity_path).to_string()
+}
+
+fn format_cell(cell: &dyn Array) -> String {
+    if cell.data_type() == &DataType::Utf8 {
+        if let Ok(texts) = <rerun::components::Text as rerun::Loggable>::from_arrow2(cell) {
+            return texts
+                .iter()
+                .map(|text| text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    }
+
+    format!("{cell:?}")
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+// ---
+
+/// Configures which timelines the recording is indexed by.
+///
+/// Rerun's builtin `log_time` (wall-clock) and `log_tick` timelines are always present.
+///
+/// Note that the Rerun Viewer (as of 0.21) cannot be told which timeline to show by default,
+/// not even through a blueprint: it picks the first non-builtin timeline in alphabetical order.
+/// That's why only `sim_time` is enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RerunTimelines {
+    /// `sim_time`: the elapsed seconds of Bevy's default [`Time`] clock.
+    pub sim_time: bool,
+
+    /// `frame`: Bevy's [`bevy::core::FrameCount`].
+    pub frame: bool,
+
+    /// `fixed_tick`: how many times `FixedUpdate` has run so far.
+    ///
+    /// Useful for physics bugs, which are keyed to fixed ticks rather than elapsed seconds.
+    pub fixed_tick: bool,
+
+    /// `virtual_time`: the elapsed seconds of the [`Time<Virtual>`] clock, which can be paused
+    /// and scaled.
+    pub virtual_time: bool,
+
+    /// `real_time`: the elapsed seconds of the [`Time<Real>`] clock, which keeps ticking even
+    /// while [`Time<Virtual>`] is paused.
+    pub real_time: bool,
+}
+
+impl Default for RerunTimelines {
+    fn default() -> Self {
+        Self {
+            sim_time: true,
+            frame: false,
+            fixed_tick: false,
+            virtual_time: false,
+            real_time: false,
+        }
+    }
+}
+
+impl RerunTimelines {
+    /// Enables all timelines.
+    pub fn all() -> Self {
+        Self {
+            sim_time: true,
+            frame: true,
+            fixed_tick: true,
+            virtual_time: true,
+            real_time: true,
+        }
+    }
+}
+
+/// Counts how many times `FixedUpdate` has run so far, for the `fixed_tick` timeline.
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub(crate) struct RerunFixedTick(pub u64);
+
+pub(crate) fn system_count_fixed_ticks(mut tick: ResMut<'_, RerunFixedTick>) {
+    tick.0 += 1;
+}
@@ -0,0 +1,228 @@
+use bevy::prelude::*;
+use revy::testing::RerunTestApp;
+
+// ---
+
+#[test]
+fn shared_meshes_are_instanced() {
+    let mut app = RerunTestApp::new();
+
+    let world = app.app.world_mut();
+    let mesh = world.resource_mut::<Assets<Mesh>>().add(Cuboid::default());
+    let material = world
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(StandardMaterial::default());
+    let rocks = (0..3)
+        .map(|i| {
+            world
+                .spawn((
+                    Mesh3d(mesh.clone()),
+                    MeshMaterial3d(material.clone()),
+                    Transform::from_xyz(i as f32, 0.0, 0.0),
+                ))
+                .id()
+        })
+        .collect::<Vec<_>>();
+    app.step(2);
+
+    app.app
+        .world_mut()
+        .entity_mut(rocks[0])
+        .insert(Transform::from_xyz(0.0, 10.0, 0.0));
+    app.step(2);
+
+    let capture = app.capture();
+
+    let instances = capture
+        .entity_paths()
+        .filter(|entity_path| entity_path.starts_with("/assets/meshes/"))
+        .collect::<Vec<_>>();
+    let [instance] = instances.as_slice() else {
+        panic!("expected a single mesh instance, got {instances:?}");
+    };
+    assert!(instance.contains("/standard_material/"));
+
+    // The mesh is logged once, its instances whenever they move.
+    assert_eq!(
+        capture
+            .values(instance, "rerun.components.Position3D")
+            .map(|values| values.len()),
+        Some(1)
+    );
+    assert_eq!(
+        capture
+            .values(instance, "rerun.components.PoseTranslation3D")
+            .map(|values| values.len()),
+        Some(2)
+    );
+
+    for rock in rocks {
+        let entity_path = format!("world/{rock:?}/comps/mesh3d");
+        assert_eq!(
+            capture.latest(&entity_path, "revy.components.MeshInstance"),
+            Some(*instance)
+        );
+        assert!(capture
+            .latest(&entity_path, "rerun.components.Position3D")
+            .is_none());
+    }
+}
+
+#[test]
+fn textures_are_stored_once_per_mesh() {
+    use bevy::render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    };
+
+    let mut app = RerunTestApp::new();
+
+    let world = app.app.world_mut();
+    let image = world.resource_mut::<Assets<Image>>().add(Image::new(
+        Extent3d::default(),
+        TextureDimension::D2,
+        vec![255; 4],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    ));
+    let material = world
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(StandardMaterial::from(image));
+    let cube = world.resource_mut::<Assets<Mesh>>().add(Cuboid::default());
+    let sphere = world.resource_mut::<Assets<Mesh>>().add(Sphere::default());
+    for mesh in [&cube, &cube, &sphere] {
+        world.spawn((
+            Mesh3d(mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::default(),
+        ));
+    }
+    app.step(3);
+
+    let capture = app.capture();
+    let images = capture
+        .entity_paths()
+        .flat_map(|entity_path| {
+            capture
+                .values(entity_path, "rerun.components.ImageBuffer")
+                .into_iter()
+                .flat_map(|values| values.values().flatten())
+        })
+        .count();
+    assert_eq!(images, 2, "one texture per mesh and material pair");
+}
+
+#[test]
+fn unused_meshes_are_cleared() {
+    let mut app = RerunTestApp::new();
+
+    let world = app.app.world_mut();
+    let mesh = world.resource_mut::<Assets<Mesh>>().add(Cuboid::default());
+    let rock = world.spawn((Mesh3d(mesh), Transform::default())).id();
+    app.step(1);
+
+    app.app.world_mut().despawn(rock);
+    app.step(1);
+
+    let capture = app.capture();
+    let instance = capture
+        .entity_paths()
+        .find(|entity_path| entity_path.starts_with("/assets/meshes/"))
+        .unwrap();
+    assert!(capture
+        .values(instance, "rerun.components.ClearIsRecursive")
+        .is_some());
+}
+
+#[test]
+fn only_dependent_components_are_relogged() {
+    let mut app = RerunTestApp::new();
+
+    // NOTE: Line meshes aren't instanced: they're logged at the entity, with their material.
+    let world = app.app.world_mut();
+    let mesh = world.resource_mut::<Assets<Mesh>>().add(
+        Mesh::new(bevy::render::mesh::PrimitiveTopology::LineList, default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vec![Vec3::ZERO, Vec3::X]),
+    );
+    let material = world
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(StandardMaterial::default());
+    let line = world
+        .spawn((Mesh3d(mesh), MeshMaterial3d(material.clone())))
+        .id();
+    app.step(2);
+
+    // Unrelated.
+    app.app
+        .world_mut()
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(StandardMaterial::default());
+    app.step(2);
+
+    app.app
+        .world_mut()
+        .resource_mut::<Assets<StandardMaterial>>()
+        .get_mut(&material)
+        .unwrap()
+        .base_color = Color::BLACK;
+    app.step(2);
+
+    let capture = app.capture();
+    let entity_path = format!("world/{line:?}/comps/mesh3d");
+    assert_eq!(
+        capture
+            .values(&entity_path, "rerun.components.Color")
+            .map(|values| values.len()),
+        Some(2)
+    );
+}
+
+#[derive(Asset, TypePath)]
+struct Heightmap(f32);
+
+#[derive(Component)]
+struct Terrain(Handle<Heightmap>);
+
+#[test]
+fn custom_asset_dependencies() {
+    use revy::{external::rerun, RerunAppExt as _, RerunLogger};
+
+    let mut app = RerunTestApp::new();
+    app.app
+        .init_asset::<Heightmap>()
+        .register_rerun_asset::<Heightmap>()
+        .register_rerun_component_logger::<Terrain>(
+            RerunLogger::typed::<Terrain, _>(|terrain, ctx| {
+                let heightmap = ctx.world.resource::<Assets<Heightmap>>().get(&terrain.0)?;
+                Some(revy::Aliased::<rerun::components::Text>::new(
+                    "Height",
+                    heightmap.0.to_string(),
+                ))
+            })
+            .with_asset_dependencies(|_world, entity| {
+                entity
+                    .get::<Terrain>()
+                    .map(|terrain| vec![terrain.0.id().untyped()])
+                    .unwrap_or_default()
+            }),
+        );
+
+    let world = app.app.world_mut();
+    let heightmap = world
+        .resource_mut::<Assets<Heightmap>>()
+        .add(Heightmap(1.0));
+    let terrain = world.spawn(Terrain(heightmap.clone())).id();
+    app.step(2);
+
+    app.app
+        .world_mut()
+        .resource_mut::<Assets<Heightmap>>()
+        .get_mut(&heightmap)
+        .unwrap()
+        .0 = 2.0;
+    app.step(2);
+
+    let capture = app.capture();
+    let entity_path = format!("world/{terrain:?}");
+    assert_eq!(capture.latest(&entity_path, "Height"), Some("2"));
+}
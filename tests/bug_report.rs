@@ -0,0 +1,112 @@
+use std::{path::PathBuf, time::Duration};
+
+use bevy::prelude::*;
+use revy::{
+    external::rerun,
+    testing::{RerunCapture, RerunTestApp},
+    BugReportSettings, BugReportWindow, RerunBugReport, RerunPlugin,
+};
+
+// ---
+
+/// Runs `frames` frames in bug report mode, then triggers a report and returns what it contains.
+///
+/// `setup` gets to configure the recording before bug report mode takes it over.
+/// A single entity is spawned on the first frame, and never modified afterwards.
+fn bug_report(
+    name: &str,
+    window: BugReportWindow,
+    frames: usize,
+    setup: impl FnOnce(&rerun::RecordingStream),
+) -> (PathBuf, RerunCapture) {
+    let output_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    std::fs::remove_dir_all(&output_dir).ok();
+
+    let mut app = RerunTestApp::with_plugin(|rec| {
+        setup(&rec);
+        RerunPlugin::new(rec).with_bug_report(BugReportSettings {
+            window,
+            segments_per_window: 2,
+            output_dir: output_dir.clone(),
+            hotkey: None,
+            on_panic: false,
+        })
+    });
+    app.app
+        .world_mut()
+        .spawn(Transform::from_xyz(1.0, 2.0, 3.0));
+    app.step(frames);
+    assert!(!output_dir.exists(), "no report was requested yet");
+
+    app.app.world_mut().send_event(RerunBugReport);
+    app.step(1);
+
+    let reports = std::fs::read_dir(&output_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    assert_eq!(reports.len(), 1);
+
+    let capture = RerunCapture::from_rrd_filepath(&reports[0]).unwrap();
+    (reports[0].clone(), capture)
+}
+
+fn translation_frames(capture: &RerunCapture) -> Vec<Option<i64>> {
+    let entity_path = capture
+        .entity_paths()
+        .find(|entity_path| entity_path.starts_with("/world/"))
+        .unwrap();
+    capture
+        .values(entity_path, "rerun.components.Translation3D")
+        .map(|values| values.keys().copied().collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn frames_window() {
+    let (_, capture) = bug_report("bug_report_frames", BugReportWindow::Frames(4), 10, |_| {});
+
+    // A new segment starts every 2 frames, each with a full snapshot of the world. The oldest
+    // ones get evicted once the next one covers the last 4 frames on its own.
+    assert_eq!(translation_frames(&capture), [Some(6), Some(8), Some(10)]);
+}
+
+#[test]
+fn duration_window() {
+    let (_, capture) = bug_report(
+        "bug_report_duration",
+        BugReportWindow::Duration(Duration::from_millis(110)),
+        20,
+        |_| {},
+    );
+
+    // A new segment starts every 55ms (i.e. every 4 frames at 60Hz), each with a full snapshot of
+    // the world. The oldest ones get evicted once the next one covers the last 110ms on its own.
+    assert_eq!(translation_frames(&capture), [Some(12), Some(16), Some(20)]);
+}
+
+#[test]
+fn reports_start_with_store_info() {
+    // NOTE: The store info of a stream that isn't buffered goes straight to its original sink,
+    // before bug report mode takes over.
+    let (path, _) = bug_report(
+        "bug_report_store_info",
+        BugReportWindow::Frames(4),
+        1,
+        |rec| {
+            let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("bug_report_store_info.rrd");
+            rec.save(path).unwrap();
+        },
+    );
+
+    let file = std::io::BufReader::new(std::fs::File::open(path).unwrap());
+    let mut decoder = rerun::external::re_log_encoding::decoder::Decoder::new(
+        rerun::external::re_log_encoding::VersionPolicy::Error,
+        file,
+    )
+    .unwrap();
+    assert!(matches!(
+        decoder.next(),
+        Some(Ok(rerun::log::LogMsg::SetStoreInfo(_)))
+    ));
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use revy::{testing::RerunTestApp, NoDedup, RerunChangeDedup};
+
+// ---
+
+/// Reflected, but not hashable: deduplicated by comparing values.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Speed(f32);
+
+#[derive(Component)]
+struct Opaque;
+
+/// Mutably accesses every component without actually changing anything.
+fn touch_everything(mut speeds: Query<&mut Speed>, mut opaques: Query<&mut Opaque>) {
+    for mut speed in &mut speeds {
+        speed.set_changed();
+    }
+    for mut opaque in &mut opaques {
+        opaque.set_changed();
+    }
+}
+
+fn logged_frames(app: &mut RerunTestApp, entity: Entity, component: &str) -> Vec<Option<i64>> {
+    app.capture()
+        .values(&format!("world/{entity:?}"), component)
+        .map(|values| values.keys().copied().collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn unchanged_values_are_deduplicated() {
+    let mut app = RerunTestApp::new();
+    app.app
+        .register_type::<Speed>()
+        .add_systems(Update, touch_everything);
+    let entity = app.app.world_mut().spawn(Speed(1.0)).id();
+    app.step(3);
+
+    app.app.world_mut().get_mut::<Speed>(entity).unwrap().0 = 2.0;
+    app.step(2);
+
+    assert_eq!(
+        logged_frames(&mut app, entity, "change_dedup.Speed"),
+        [Some(0), Some(3)]
+    );
+}
+
+#[test]
+fn unreflected_components_are_always_changed() {
+    let mut app = RerunTestApp::new();
+    app.app.add_systems(Update, touch_everything);
+    let entity = app.app.world_mut().spawn(Opaque).id();
+    app.step(3);
+
+    assert_eq!(
+        logged_frames(&mut app, entity, "change_dedup.Opaque"),
+        [Some(0), Some(1), Some(2)]
+    );
+}
+
+#[test]
+fn per_type_strategy() {
+    let mut app = RerunTestApp::new();
+    app.app
+        .register_type::<Speed>()
+        .insert_resource(RerunChangeDedup::default().with::<Speed>(NoDedup))
+        .add_systems(Update, touch_everything);
+    let entity = app.app.world_mut().spawn(Speed(1.0)).id();
+    app.step(3);
+
+    assert_eq!(
+        logged_frames(&mut app, entity, "change_dedup.Speed"),
+        [Some(0), Some(1), Some(2)]
+    );
+}
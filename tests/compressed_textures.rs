@@ -0,0 +1,82 @@
+#![cfg(feature = "compressed_textures")]
+
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureFormat},
+};
+use revy::{decompress, RerunCompressedTextures};
+
+// ---
+
+/// A single 4x4 block, all pixels pure red.
+const BC1_RED: [u8; 8] = [0x00, 0xf8, 0x00, 0x00, 0, 0, 0, 0];
+
+/// A single 4x4 block, all pixels set to 200.
+const BC4_200: [u8; 8] = [200, 0, 0, 0, 0, 0, 0, 0];
+
+// NOTE: `Image::new` cannot be used with compressed formats.
+fn compressed_image(format: TextureFormat, width: u32, height: u32, data: Vec<u8>) -> Image {
+    let mut image = Image { data, ..default() };
+    image.texture_descriptor.format = format;
+    image.texture_descriptor.size = Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    image
+}
+
+#[test]
+fn decompress_bc1() {
+    let image = compressed_image(TextureFormat::Bc1RgbaUnormSrgb, 4, 4, BC1_RED.to_vec());
+    let decoded = decompress(&image).unwrap();
+
+    assert_eq!(
+        decoded.texture_descriptor.format,
+        TextureFormat::Bgra8UnormSrgb
+    );
+    assert_eq!(decoded.data, [0, 0, 255, 255].repeat(16));
+}
+
+#[test]
+fn decompress_bc4() {
+    let image = compressed_image(TextureFormat::Bc4RUnorm, 4, 4, BC4_200.to_vec());
+    let decoded = decompress(&image).unwrap();
+
+    assert_eq!(decoded.texture_descriptor.format, TextureFormat::R8Unorm);
+    assert_eq!(decoded.data, vec![200; 16]);
+}
+
+#[test]
+fn decompress_partial_blocks_and_mips() {
+    // A 2x2 image still takes a full block, and is followed by its 1x1 mip.
+    let data = [BC1_RED, BC1_RED].concat();
+    let image = compressed_image(TextureFormat::Bc1RgbaUnorm, 2, 2, data);
+    let decoded = decompress(&image).unwrap();
+
+    assert_eq!(decoded.texture_descriptor.format, TextureFormat::Bgra8Unorm);
+    assert_eq!(decoded.data, [0, 0, 255, 255].repeat(4));
+
+    // Not enough data.
+    let image = compressed_image(TextureFormat::Bc1RgbaUnorm, 8, 8, BC1_RED.to_vec());
+    assert!(decompress(&image).is_none());
+}
+
+#[test]
+fn size_cap_and_cache() {
+    let image = compressed_image(TextureFormat::Bc1RgbaUnormSrgb, 4, 4, BC1_RED.to_vec());
+    let id = AssetId::<Image>::default();
+
+    let textures = RerunCompressedTextures::new(8);
+    assert!(textures.get_or_decode(id, &image).is_none());
+
+    // Cached: raising the cap doesn't change anything until the texture gets evicted.
+    let mut textures = textures;
+    textures.max_pixels = 16;
+    assert!(textures.get_or_decode(id, &image).is_none());
+
+    textures.evict(id);
+    let (format, buffer) = textures.get_or_decode(id, &image).unwrap();
+    assert_eq!((format.width, format.height), (4, 4));
+    assert_eq!(buffer.0.len(), 4 * 16);
+}
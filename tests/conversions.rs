@@ -0,0 +1,400 @@
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, MeshVertexAttribute, PrimitiveTopology, VertexAttributeValues},
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, VertexFormat},
+    },
+};
+use revy::{external::rerun, RerunImage, RerunMesh, ToRerun as _};
+
+// ---
+
+fn mesh(topology: PrimitiveTopology, indices: Option<Vec<u32>>) -> Mesh {
+    let mut mesh = Mesh::new(topology, RenderAssetUsages::default()).with_inserted_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0],
+        ],
+    );
+    if let Some(indices) = indices {
+        mesh.insert_indices(Indices::U32(indices));
+    }
+    mesh
+}
+
+#[test]
+fn triangle_strip() {
+    let Some(RerunMesh::Mesh3D(mesh)) = mesh(PrimitiveTopology::TriangleStrip, None).to_rerun()
+    else {
+        panic!("expected a Mesh3D");
+    };
+
+    let triangles = mesh
+        .triangle_indices
+        .unwrap()
+        .iter()
+        .map(|triangle| triangle.0 .0)
+        .collect::<Vec<_>>();
+    assert_eq!(triangles, vec![[0, 1, 2], [2, 1, 3]]);
+}
+
+#[test]
+fn triangle_strip_with_restart() {
+    let indices = vec![0, 1, 2, u32::MAX, 1, 2, 3];
+    let Some(RerunMesh::Mesh3D(mesh)) =
+        mesh(PrimitiveTopology::TriangleStrip, Some(indices)).to_rerun()
+    else {
+        panic!("expected a Mesh3D");
+    };
+
+    let triangles = mesh
+        .triangle_indices
+        .unwrap()
+        .iter()
+        .map(|triangle| triangle.0 .0)
+        .collect::<Vec<_>>();
+    assert_eq!(triangles, vec![[0, 1, 2], [1, 2, 3]]);
+}
+
+#[test]
+fn line_list() {
+    let Some(RerunMesh::LineStrips3D(strips)) =
+        mesh(PrimitiveTopology::LineList, Some(vec![0, 1, 2, 3])).to_rerun()
+    else {
+        panic!("expected LineStrips3D");
+    };
+
+    assert_eq!(strips.strips.len(), 2);
+    assert_eq!(strips.strips[0].0.len(), 2);
+}
+
+#[test]
+fn line_strip() {
+    let Some(RerunMesh::LineStrips3D(strips)) = mesh(PrimitiveTopology::LineStrip, None).to_rerun()
+    else {
+        panic!("expected LineStrips3D");
+    };
+
+    assert_eq!(strips.strips.len(), 1);
+    assert_eq!(strips.strips[0].0.len(), 4);
+}
+
+#[test]
+fn point_list() {
+    let Some(RerunMesh::Points3D(points)) =
+        mesh(PrimitiveTopology::PointList, Some(vec![3, 0])).to_rerun()
+    else {
+        panic!("expected Points3D");
+    };
+
+    assert_eq!(points.positions.len(), 2);
+    assert_eq!(points.positions[0], [1.0, 1.0, 0.0].into());
+}
+
+#[test]
+fn out_of_bounds_indices() {
+    let Some(RerunMesh::LineStrips3D(lines)) =
+        mesh(PrimitiveTopology::LineList, Some(vec![0, 1, 2, 9])).to_rerun()
+    else {
+        panic!("expected LineStrips3D");
+    };
+    assert_eq!(lines.strips.len(), 1);
+
+    // NOTE: List topologies don't support primitive restart.
+    let Some(RerunMesh::Points3D(points)) =
+        mesh(PrimitiveTopology::PointList, Some(vec![3, u32::MAX, 9, 0])).to_rerun()
+    else {
+        panic!("expected Points3D");
+    };
+    assert_eq!(points.positions.len(), 2);
+
+    let Some(RerunMesh::LineStrips3D(strip)) =
+        mesh(PrimitiveTopology::LineStrip, Some(vec![0, 9, 1])).to_rerun()
+    else {
+        panic!("expected LineStrips3D");
+    };
+    assert_eq!(strip.strips[0].0.len(), 2);
+}
+
+#[test]
+fn vertex_colors() {
+    let expected = vec![
+        rerun::Color::from_unmultiplied_rgba(255, 0, 0, 255),
+        rerun::Color::from_unmultiplied_rgba(0, 128, 0, 255),
+        rerun::Color::from_unmultiplied_rgba(0, 0, 0, 255),
+        rerun::Color::from_unmultiplied_rgba(255, 255, 255, 255),
+    ];
+
+    let colors = [
+        VertexAttributeValues::Float32x3(vec![
+            [1.0, 0.0, 0.0],
+            [0.0, 0.214_041_14, 0.0],
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+        ]),
+        VertexAttributeValues::Float32x4(vec![
+            [1.0, 0.0, 0.0, 1.0],
+            [0.0, 0.214_041_14, 0.0, 1.0],
+            [0.0, 0.0, 0.0, 1.0],
+            [1.0, 1.0, 1.0, 1.0],
+        ]),
+        VertexAttributeValues::Unorm8x4(vec![
+            [255, 0, 0, 255],
+            [0, 55, 0, 255],
+            [0, 0, 0, 255],
+            [255, 255, 255, 255],
+        ]),
+    ];
+
+    for colors in colors {
+        // NOTE: Bevy only accepts other formats through custom attributes sharing the same id.
+        let attribute = MeshVertexAttribute::new("Vertex_Color", 5, VertexFormat::from(&colors));
+        let mesh =
+            mesh(PrimitiveTopology::TriangleList, None).with_inserted_attribute(attribute, colors);
+        let Some(RerunMesh::Mesh3D(mesh)) = mesh.to_rerun() else {
+            panic!("expected a Mesh3D");
+        };
+
+        assert_eq!(mesh.vertex_colors, Some(expected.clone()));
+    }
+}
+
+#[test]
+fn position_formats() {
+    let mesh = Mesh::new(PrimitiveTopology::PointList, RenderAssetUsages::default())
+        .with_inserted_attribute(
+            MeshVertexAttribute::new("Vertex_Position", 0, VertexFormat::Float32x2),
+            vec![[1.0_f32, 2.0]],
+        );
+    let Some(RerunMesh::Points3D(points)) = mesh.to_rerun() else {
+        panic!("expected Points3D");
+    };
+
+    assert_eq!(points.positions, vec![[1.0, 2.0, 0.0].into()]);
+}
+
+// ---
+
+fn new_image(format: TextureFormat, data: Vec<u8>) -> Image {
+    let size = Extent3d {
+        width: 1,
+        height: 1,
+        depth_or_array_layers: 1,
+    };
+    Image::new(
+        size,
+        TextureDimension::D2,
+        data,
+        format,
+        RenderAssetUsages::default(),
+    )
+}
+
+fn image_format(image: &RerunImage) -> rerun::datatypes::ImageFormat {
+    match image {
+        RerunImage::Image(image) => image.format.0,
+        RerunImage::DepthImage(image) => image.format.0,
+    }
+}
+
+fn image_buffer(image: &RerunImage) -> Vec<u8> {
+    let buffer = match image {
+        RerunImage::Image(image) => &image.buffer,
+        RerunImage::DepthImage(image) => &image.buffer,
+    };
+    buffer.0.to_vec()
+}
+
+#[test]
+fn image_formats() {
+    use rerun::datatypes::{ChannelDatatype as D, ColorModel as C};
+
+    let formats = [
+        (TextureFormat::R8Unorm, 1, C::L, D::U8),
+        (TextureFormat::R16Unorm, 2, C::L, D::U16),
+        (TextureFormat::R16Sint, 2, C::L, D::I16),
+        (TextureFormat::R16Float, 2, C::L, D::F16),
+        (TextureFormat::R32Float, 4, C::L, D::F32),
+        (TextureFormat::Rgba8UnormSrgb, 4, C::RGBA, D::U8),
+        (TextureFormat::Bgra8UnormSrgb, 4, C::BGRA, D::U8),
+        (TextureFormat::Rgba16Float, 8, C::RGBA, D::F16),
+        (TextureFormat::Rgba32Float, 16, C::RGBA, D::F32),
+    ];
+
+    for (format, size, color_model, datatype) in formats {
+        let image = new_image(format, vec![0; size]);
+        let Some(converted @ RerunImage::Image(_)) = image.to_rerun() else {
+            panic!("expected an Image for {format:?}");
+        };
+
+        let converted = image_format(&converted);
+        assert_eq!(converted.color_model, Some(color_model), "{format:?}");
+        assert_eq!(converted.channel_datatype, Some(datatype), "{format:?}");
+    }
+}
+
+#[test]
+fn image_dual_channel() {
+    let image = new_image(TextureFormat::Rg16Uint, vec![1, 2, 3, 4]);
+    let Some(converted) = image.to_rerun() else {
+        panic!("expected an Image");
+    };
+
+    assert_eq!(
+        image_format(&converted).color_model,
+        Some(rerun::datatypes::ColorModel::RGB)
+    );
+    assert_eq!(image_buffer(&converted), vec![1, 2, 3, 4, 0, 0]);
+}
+
+#[test]
+fn image_depth() {
+    let depth = 0.5_f32.to_le_bytes().to_vec();
+    let image = new_image(TextureFormat::Depth32Float, depth.clone());
+    let Some(converted @ RerunImage::DepthImage(_)) = image.to_rerun() else {
+        panic!("expected a DepthImage");
+    };
+
+    assert_eq!(
+        image_format(&converted).channel_datatype,
+        Some(rerun::datatypes::ChannelDatatype::F32)
+    );
+    assert_eq!(image_buffer(&converted), depth);
+
+    // Depth can't be used as a texture.
+    let texture: Option<(
+        rerun::components::ImageFormat,
+        rerun::components::ImageBuffer,
+    )> = image.to_rerun();
+    assert!(texture.is_none());
+}
+
+#[test]
+fn image_color_spaces() {
+    fn f16s(values: [f32; 4]) -> Vec<u8> {
+        values
+            .into_iter()
+            .flat_map(|v| half::f16::from_f32(v).to_le_bytes())
+            .collect()
+    }
+    fn f32s(values: [f32; 4]) -> Vec<u8> {
+        values.into_iter().flat_map(f32::to_le_bytes).collect()
+    }
+
+    // Linear 0.214 is sRGB 0.5: alpha is always left untouched.
+    let linear = [0.214, 0.214, 0.214, 0.214];
+    let srgb = [0.5, 0.5, 0.5, 0.214];
+
+    let cases = [
+        // Already sRGB.
+        (TextureFormat::Rgba8UnormSrgb, vec![55; 4], vec![55; 4]),
+        (TextureFormat::Bgra8UnormSrgb, vec![55; 4], vec![55; 4]),
+        // Linear color.
+        (
+            TextureFormat::Rgba8Unorm,
+            vec![55; 4],
+            vec![128, 128, 128, 55],
+        ),
+        (
+            TextureFormat::Bgra8Unorm,
+            vec![55; 4],
+            vec![128, 128, 128, 55],
+        ),
+        (
+            TextureFormat::Rgba16Unorm,
+            [14025_u16; 4]
+                .into_iter()
+                .flat_map(u16::to_le_bytes)
+                .collect(),
+            [32768_u16, 32768, 32768, 14025]
+                .into_iter()
+                .flat_map(u16::to_le_bytes)
+                .collect(),
+        ),
+        (TextureFormat::Rgba16Float, f16s(linear), f16s(srgb)),
+        (TextureFormat::Rgba32Float, f32s(linear), f32s(srgb)),
+        // Data rather than color.
+        (TextureFormat::R8Unorm, vec![55], vec![55]),
+        (
+            TextureFormat::R32Float,
+            f32s(linear)[..4].to_vec(),
+            f32s(linear)[..4].to_vec(),
+        ),
+        (TextureFormat::Rgba8Uint, vec![55; 4], vec![55; 4]),
+        (TextureFormat::Rgba8Snorm, vec![55; 4], vec![55; 4]),
+    ];
+
+    for (format, data, expected) in cases {
+        let Some(converted) = new_image(format, data).to_rerun() else {
+            panic!("expected an Image for {format:?}");
+        };
+        let converted = image_buffer(&converted);
+
+        // NOTE: Floats only need to be close enough.
+        let close_enough = match format {
+            TextureFormat::Rgba16Float => converted
+                .chunks_exact(2)
+                .zip(expected.chunks_exact(2))
+                .all(|(a, b)| {
+                    let a = half::f16::from_le_bytes([a[0], a[1]]).to_f32();
+                    let b = half::f16::from_le_bytes([b[0], b[1]]).to_f32();
+                    (a - b).abs() < 0.01
+                }),
+            TextureFormat::Rgba32Float => converted
+                .chunks_exact(4)
+                .zip(expected.chunks_exact(4))
+                .all(|(a, b)| {
+                    let a = f32::from_le_bytes([a[0], a[1], a[2], a[3]]);
+                    let b = f32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+                    (a - b).abs() < 0.01
+                }),
+            TextureFormat::Rgba16Unorm => converted
+                .chunks_exact(2)
+                .zip(expected.chunks_exact(2))
+                .all(|(a, b)| {
+                    let a = u16::from_le_bytes([a[0], a[1]]) as i32;
+                    let b = u16::from_le_bytes([b[0], b[1]]) as i32;
+                    (a - b).abs() < 100
+                }),
+            _ => converted == expected,
+        };
+        assert!(close_enough, "{format:?}: {converted:?} != {expected:?}");
+    }
+}
+
+#[test]
+fn image_arrays_unsupported() {
+    let size = Extent3d {
+        width: 1,
+        height: 1,
+        depth_or_array_layers: 2,
+    };
+    let image = Image::new(
+        size,
+        TextureDimension::D2,
+        vec![0; 8],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    let converted: Option<RerunImage> = image.to_rerun();
+    assert!(converted.is_none());
+}
+
+#[test]
+fn projection_to_pinhole() {
+    let perspective: rerun::Pinhole =
+        Projection::Perspective(PerspectiveProjection::default()).to_rerun();
+    assert!(perspective.resolution.is_some());
+
+    // Orthographic projections can't be represented by a pinhole: only the axes are kept.
+    let orthographic: rerun::Pinhole =
+        Projection::Orthographic(OrthographicProjection::default_3d()).to_rerun();
+    assert_eq!(
+        orthographic.camera_xyz,
+        Some(rerun::components::ViewCoordinates::RUB)
+    );
+}
@@ -0,0 +1,500 @@
+use bevy::prelude::*;
+use revy::external::rerun;
+use revy::testing::RerunTestApp;
+
+// ---
+
+#[test]
+fn transform_and_name() {
+    let mut app = RerunTestApp::new();
+    let entity = app
+        .app
+        .world_mut()
+        .spawn((Name::new("player"), Transform::from_xyz(1.0, 2.0, 3.0)))
+        .id();
+    app.step(2);
+
+    app.app
+        .world_mut()
+        .entity_mut(entity)
+        .insert(Transform::from_xyz(4.0, 5.0, 6.0));
+    app.step(2);
+
+    let capture = app.capture();
+    let entity_path = format!("world/{entity:?}_player");
+
+    assert!(capture
+        .components(&entity_path)
+        .any(|component| component == "rerun.components.Translation3D"));
+    assert!(capture
+        .values(&entity_path, "rerun.components.Translation3D")
+        .is_some_and(|values| values.len() == 2));
+
+    capture
+        // NOTE: `Name`'s precomputed hash isn't stable across builds.
+        .filtered(|entity_path, component| {
+            entity_path.starts_with("/world") && component != "bevy_core.name.Name"
+        })
+        .assert_snapshot("tests/snapshots/transform_and_name.txt");
+}
+
+#[test]
+#[should_panic = "line endings or trailing newlines differ"]
+fn snapshot_line_endings() {
+    let mut app = RerunTestApp::new();
+    app.app.world_mut().spawn(Name::new("player"));
+    app.step(1);
+
+    let capture = app.capture();
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("snapshot_line_endings.txt");
+    std::fs::write(&path, capture.to_snapshot().replace('\n', "\r\n")).unwrap();
+
+    capture.assert_snapshot(path);
+}
+
+#[test]
+fn reparenting() {
+    let mut app = RerunTestApp::new();
+    let world = app.app.world_mut();
+    let old_parent = world.spawn(Name::new("old")).id();
+    let new_parent = world.spawn(Name::new("new")).id();
+    let child = world
+        .spawn((Name::new("child"), Transform::from_xyz(1.0, 2.0, 3.0)))
+        .set_parent(old_parent)
+        .id();
+    let grandchild = world
+        .spawn((Name::new("grandchild"), Transform::from_xyz(4.0, 5.0, 6.0)))
+        .set_parent(child)
+        .id();
+    app.step(2);
+
+    app.app.world_mut().entity_mut(child).set_parent(new_parent);
+    app.step(2);
+
+    let capture = app.capture();
+    let old_path = format!("world/{old_parent:?}_old/{child:?}_child");
+    let new_path = format!("world/{new_parent:?}_new/{child:?}_child");
+
+    // The whole subtree is cleared at its old path…
+    let clears = capture
+        .values(&old_path, "rerun.components.ClearIsRecursive")
+        .expect("old path should have been cleared");
+    assert_eq!(clears.keys().copied().collect::<Vec<_>>(), [Some(2)]);
+
+    // …and fully re-logged at its new one, descendants included, even though none of their
+    // components changed.
+    for entity_path in [
+        new_path.clone(),
+        format!("{new_path}/{grandchild:?}_grandchild"),
+    ] {
+        let translations = capture
+            .values(&entity_path, "rerun.components.Translation3D")
+            .unwrap_or_else(|| panic!("{entity_path} should have been re-logged"));
+        assert_eq!(translations.keys().copied().collect::<Vec<_>>(), [Some(2)]);
+    }
+}
+
+#[derive(Component)]
+struct Health(u32);
+
+#[test]
+fn typed_logger() {
+    use revy::RerunAppExt as _;
+
+    let mut app = RerunTestApp::new();
+    app.app
+        .register_rerun_logger::<Health, _>(|health, _ctx| {
+            Some(revy::Aliased::<rerun::components::Text>::new(
+                "Health",
+                health.0.to_string(),
+            ))
+        })
+        .ignore_rerun_component::<Transform>();
+
+    let entity = app
+        .app
+        .world_mut()
+        .spawn((Health(42), Transform::default()))
+        .id();
+    app.step(1);
+
+    let capture = app.capture();
+    let entity_path = format!("world/{entity:?}");
+
+    assert_eq!(capture.latest(&entity_path, "Health"), Some("42"));
+    assert!(capture
+        .latest(&entity_path, "rerun.components.Translation3D")
+        .is_none());
+}
+
+#[test]
+fn logger_chain() {
+    use revy::{RerunAppExt as _, ToRerun as _};
+
+    let mut app = RerunTestApp::new();
+    app.app
+        .add_rerun_logger::<Transform, _>("forward", |transform, _ctx| {
+            Some(rerun::Arrows3D::from_vectors([transform
+                .forward()
+                .as_vec3()
+                .to_rerun()]))
+        })
+        .rerun_log_reflected::<Transform>();
+
+    let entity = app.app.world_mut().spawn(Transform::default()).id();
+    app.step(1);
+
+    let capture = app.capture();
+    let entity_path = format!("world/{entity:?}");
+
+    assert!(capture
+        .latest(&entity_path, "rerun.components.Translation3D")
+        .is_some());
+    assert!(capture
+        .latest(
+            &entity_path,
+            "bevy_transform.components.transform.Transform"
+        )
+        .is_some_and(|ron| ron.contains("translation")));
+    assert!(capture
+        .latest(
+            &format!("{entity_path}/comps/forward"),
+            "rerun.components.Vector3D"
+        )
+        .is_some_and(|vectors| vectors.contains("-1]")));
+}
+
+#[test]
+fn replaced_logger_keeps_chain() {
+    use revy::RerunAppExt as _;
+
+    let mut app = RerunTestApp::new();
+    app.app
+        .add_rerun_logger::<Transform, _>("scale", |transform, _ctx| {
+            Some(rerun::Scalar::new(f64::from(transform.scale.x)))
+        })
+        .rerun_log_reflected::<Transform>()
+        .register_rerun_logger::<Transform, _>(|transform, _ctx| {
+            Some(rerun::Points3D::new([transform.translation.to_array()]))
+        });
+
+    let entity = app.app.world_mut().spawn(Transform::default()).id();
+    app.step(1);
+
+    let capture = app.capture();
+    let entity_path = format!("world/{entity:?}");
+
+    assert!(capture
+        .latest(&entity_path, "rerun.components.Position3D")
+        .is_some());
+    assert!(capture
+        .latest(&entity_path, "rerun.components.Translation3D")
+        .is_none());
+    assert!(capture
+        .latest(
+            &entity_path,
+            "bevy_transform.components.transform.Transform"
+        )
+        .is_some());
+    assert!(capture
+        .latest(
+            &format!("{entity_path}/comps/scale"),
+            "rerun.components.Scalar"
+        )
+        .is_some());
+}
+
+#[test]
+fn lights() {
+    use revy::RerunPlugin;
+
+    let mut app = RerunTestApp::with_plugin(|rec| RerunPlugin::new(rec).with_resources(true));
+    app.app.insert_resource(AmbientLight {
+        color: Color::WHITE,
+        brightness: 80.0,
+    });
+    let point = app
+        .app
+        .world_mut()
+        .spawn(PointLight {
+            range: 5.0,
+            ..default()
+        })
+        .id();
+    let spot = app.app.world_mut().spawn(SpotLight::default()).id();
+    let wide_spot = app
+        .app
+        .world_mut()
+        .spawn(SpotLight {
+            range: 2.0,
+            outer_angle: std::f32::consts::PI,
+            ..default()
+        })
+        .id();
+    let directional = app.app.world_mut().spawn(DirectionalLight::default()).id();
+    app.step(1);
+
+    let capture = app.capture();
+    let light_path = |entity: Entity| format!("world/{entity:?}/comps/light");
+
+    assert!(capture
+        .latest(&light_path(point), "rerun.components.Position3D")
+        .is_some());
+    assert!(capture
+        .latest(&light_path(point), "rerun.components.HalfSize3D")
+        .is_none());
+    assert!(capture
+        .latest(
+            &format!("world/{point:?}/comps/light_range"),
+            "rerun.components.HalfSize3D"
+        )
+        .is_some_and(|half_sizes| half_sizes.contains("[5, 5, 5]")));
+    assert!(capture
+        .latest(&light_path(spot), "rerun.components.LineStrip3D")
+        .is_some());
+    // Angles past π/2 are clamped, making for a half-sphere that's as wide as the range.
+    assert!(capture
+        .latest(&light_path(wide_spot), "rerun.components.LineStrip3D")
+        .is_some_and(|strips| strips.contains("[[0, 0, 0], [2, 0, ")));
+    assert!(capture
+        .latest(&light_path(directional), "rerun.components.Vector3D")
+        .is_some_and(|vectors| vectors.contains("[0, 0, -1]")));
+
+    let ambient_path = format!("resources/{}", std::any::type_name::<AmbientLight>());
+    assert_eq!(
+        capture.latest(&ambient_path, "rerun.components.Scalar"),
+        Some("Float64[80]")
+    );
+}
+
+#[test]
+fn orthographic_cameras() {
+    use bevy::{
+        render::camera::ScalingMode,
+        window::{PrimaryWindow, WindowResolution},
+    };
+
+    let mut app = RerunTestApp::new();
+    let world = app.app.world_mut();
+    world.spawn((
+        Window {
+            resolution: WindowResolution::new(200.0, 100.0),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    let window_sized = world
+        .spawn(OrthographicProjection {
+            near: 0.0,
+            far: 10.0,
+            ..OrthographicProjection::default_3d()
+        })
+        .id();
+    let fixed = world
+        .spawn(Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: 4.0,
+                height: 2.0,
+            },
+            near: 0.0,
+            far: 10.0,
+            ..OrthographicProjection::default_3d()
+        }))
+        .id();
+    let perspective = world.spawn(PerspectiveProjection::default()).id();
+    app.step(1);
+
+    let capture = app.capture();
+    let cam_path = |entity: Entity| format!("world/{entity:?}/comps/cam");
+
+    // The area follows the size of the primary window.
+    assert!(capture
+        .latest(&cam_path(window_sized), "rerun.components.HalfSize3D")
+        .is_some_and(|half_sizes| half_sizes.contains("[100, 50, 5]")));
+    assert!(capture
+        .latest(
+            &cam_path(window_sized),
+            "rerun.components.PoseTranslation3D"
+        )
+        .is_some_and(|centers| centers.contains("[0, 0, -5]")));
+
+    assert!(capture
+        .latest(&cam_path(fixed), "rerun.components.HalfSize3D")
+        .is_some_and(|half_sizes| half_sizes.contains("[2, 1, 5]")));
+
+    assert!(capture
+        .latest(&cam_path(perspective), "rerun.components.PinholeProjection")
+        .is_some());
+}
+
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct Score(u32);
+
+#[derive(Resource, Reflect, Default)]
+#[reflect(Resource)]
+struct Difficulty(u32);
+
+#[derive(Resource)]
+struct Unreflected;
+
+#[test]
+fn resources() {
+    use revy::{RerunComponentLoggers, RerunLogger, RerunPlugin};
+
+    let mut app = RerunTestApp::with_plugin(|rec| RerunPlugin::new(rec).with_resources(true));
+    app.app
+        .register_type::<Score>()
+        .register_type::<Difficulty>()
+        .insert_resource(Score(3))
+        .insert_resource(Difficulty(1))
+        .insert_resource(Unreflected)
+        .insert_resource(RerunComponentLoggers::new([(
+            std::any::type_name::<Score>().into(),
+            Some(RerunLogger::typed_resource::<Score, _>(|score, _world| {
+                Some(revy::Aliased::<rerun::components::Text>::new(
+                    "Score",
+                    score.0.to_string(),
+                ))
+            })),
+        )]));
+    app.step(1);
+
+    let capture = app.capture();
+    let resource_path = |name: &str| format!("resources/{name}");
+
+    assert_eq!(
+        capture.latest(&resource_path(std::any::type_name::<Score>()), "Score"),
+        Some("3")
+    );
+    assert!(capture
+        .components(&resource_path(std::any::type_name::<Difficulty>()))
+        .next()
+        .is_some());
+    assert!(capture
+        .components(&resource_path(std::any::type_name::<Unreflected>()))
+        .next()
+        .is_none());
+}
+
+#[test]
+fn plugin_struct_literal() {
+    use revy::RerunPlugin;
+
+    let mut app = RerunTestApp::with_plugin(|rec| RerunPlugin {
+        sync_resources: true,
+        ..rec.into()
+    });
+    app.app
+        .register_type::<Difficulty>()
+        .insert_resource(Difficulty(1));
+    app.step(1);
+
+    let capture = app.capture();
+    assert!(capture
+        .components(&format!(
+            "resources/{}",
+            std::any::type_name::<Difficulty>()
+        ))
+        .next()
+        .is_some());
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_get_component_logger() {
+    use revy::{get_component_logger, DefaultRerunComponentLoggers, RerunComponentLoggers};
+
+    let mut app = RerunTestApp::new();
+    app.step(1);
+
+    let world = app.app.world();
+    let component = |id| world.components().get_info(id).unwrap();
+    let default_loggers = world.resource::<DefaultRerunComponentLoggers>();
+    let disabled = RerunComponentLoggers::new([(std::any::type_name::<Transform>().into(), None)]);
+
+    let transform = component(world.component_id::<Transform>().unwrap());
+    assert!(get_component_logger(transform, None, default_loggers).is_some());
+    assert!(get_component_logger(transform, Some(&disabled), default_loggers).is_none());
+}
+
+#[derive(Event, Reflect)]
+struct Collision {
+    damage: u32,
+}
+
+#[test]
+fn reflected_events() {
+    use revy::RerunAppExt as _;
+
+    let mut app = RerunTestApp::new();
+    // NOTE: `Collision` is never registered explicitly.
+    app.app.rerun_log_events::<Collision>();
+    app.app.world_mut().send_event(Collision { damage: 42 });
+    app.step(1);
+
+    let capture = app.capture();
+    let entity_path = format!("events/{}", std::any::type_name::<Collision>());
+    assert!(capture
+        .latest(&entity_path, "rerun.components.Text")
+        .is_some_and(|body| body.contains("damage: 42")));
+}
+
+#[derive(States, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+enum GameState {
+    #[default]
+    Menu,
+    Playing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct InGame;
+
+impl ComputedStates for InGame {
+    type SourceStates = GameState;
+
+    fn compute(sources: GameState) -> Option<Self> {
+        (sources == GameState::Playing).then_some(Self)
+    }
+}
+
+#[test]
+fn states() {
+    use revy::RerunAppExt as _;
+
+    let mut app = RerunTestApp::new();
+    app.app
+        .add_plugins(bevy::state::app::StatesPlugin)
+        .init_state::<GameState>()
+        .add_computed_state::<InGame>()
+        .rerun_log_state_requests::<GameState>()
+        .rerun_log_state::<InGame>();
+    app.step(1);
+
+    // NOTE: Requests made before the frame starts are applied before they get a chance to be
+    // logged.
+    app.app.add_systems(
+        Update,
+        |state: Res<State<GameState>>, mut next: ResMut<NextState<GameState>>| {
+            if *state.get() == GameState::Menu {
+                next.set(GameState::Playing);
+            }
+        },
+    );
+    app.step(2);
+
+    let capture = app.capture();
+    let state_path = |name: &str| format!("states/{name}");
+
+    let game_state = state_path(std::any::type_name::<GameState>());
+    assert_eq!(capture.latest(&game_state, "State"), Some("Playing"));
+    assert!(capture
+        .values(&game_state, "rerun.components.Text")
+        .is_some_and(|logs| logs
+            .values()
+            .flatten()
+            .any(|log| log.contains("requested: Playing"))));
+
+    let in_game = state_path(std::any::type_name::<InGame>());
+    assert_eq!(capture.latest(&in_game, "State"), Some("InGame"));
+}
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use revy::{testing::RerunTestApp, ReflectRerunLogger, RerunAppExt as _, RerunLog};
+
+// ---
+
+#[derive(Component, RerunLog)]
+#[rerun(archetype = Points3D, suffix = "target")]
+struct AiTarget {
+    #[rerun(positions)]
+    position: Vec3,
+    #[rerun(colors)]
+    color: Color,
+    #[rerun(radii)]
+    radius: f32,
+    #[rerun(labels)]
+    name: String,
+
+    #[allow(dead_code)]
+    ignored: u32,
+}
+
+#[derive(Component, RerunLog)]
+#[rerun(archetype = Points3D, suffix = "waypoints")]
+struct Waypoints(#[rerun(positions, iter)] Vec<Vec3>);
+
+#[derive(Component, RerunLog)]
+#[rerun(archetype = revy::external::rerun::archetypes::Arrows3D)]
+struct Velocity(#[rerun(vectors)] Vec3);
+
+#[test]
+fn derive_rerun_log() {
+    let mut app = RerunTestApp::new();
+    app.app
+        .register_rerun_log::<AiTarget>()
+        .register_rerun_log::<Waypoints>()
+        .register_rerun_log::<Velocity>();
+
+    let entity = app
+        .app
+        .world_mut()
+        .spawn((
+            AiTarget {
+                position: Vec3::new(1.0, 2.0, 3.0),
+                color: Color::srgb(1.0, 0.0, 0.0),
+                radius: 0.5,
+                name: "target".to_owned(),
+                ignored: 0,
+            },
+            Waypoints(vec![Vec3::X, Vec3::Y]),
+            Velocity(Vec3::Z),
+        ))
+        .id();
+    app.step(1);
+
+    let capture = app.capture();
+    let entity_path = format!("world/{entity:?}");
+    let target_path = format!("{entity_path}/comps/target");
+
+    assert_eq!(
+        capture.latest(&target_path, "rerun.components.Text"),
+        Some("target")
+    );
+    assert!(capture
+        .latest(&target_path, "rerun.components.Position3D")
+        .is_some_and(|positions| positions.contains("[1, 2, 3]")));
+    assert!(capture
+        .latest(&target_path, "rerun.components.Radius")
+        .is_some_and(|radii| radii.contains("0.5")));
+    assert!(capture
+        .latest(
+            &format!("{entity_path}/comps/waypoints"),
+            "rerun.components.Position3D"
+        )
+        .is_some_and(|positions| positions.contains("[1, 0, 0], [0, 1, 0]")));
+    assert!(capture
+        .latest(&entity_path, "rerun.components.Vector3D")
+        .is_some_and(|vectors| vectors.contains("[0, 0, 1]")));
+}
+
+#[derive(Component, Reflect, RerunLog)]
+#[reflect(Component, RerunLogger)]
+#[rerun(archetype = Points3D)]
+struct Waypoint(#[rerun(positions)] Vec3);
+
+#[test]
+fn reflect_rerun_logger() {
+    let mut app = RerunTestApp::new();
+    app.app.register_type::<Waypoint>();
+
+    let entity = app.app.world_mut().spawn(Waypoint(Vec3::ONE)).id();
+    app.step(1);
+
+    let capture = app.capture();
+    let entity_path = format!("world/{entity:?}");
+
+    assert!(capture
+        .latest(&entity_path, "rerun.components.Position3D")
+        .is_some_and(|positions| positions.contains("[1, 1, 1]")));
+    assert!(capture
+        .components(&entity_path)
+        .all(|component| !component.contains("Waypoint")));
+}
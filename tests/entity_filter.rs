@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use revy::{testing::RerunTestApp, RerunEntityPath, RerunIgnore};
+
+// ---
+
+#[test]
+fn ignored_entities_are_relogged_once_unignored() {
+    let mut app = RerunTestApp::new();
+    let world = app.app.world_mut();
+    let entity = world.spawn(Transform::from_xyz(1.0, 2.0, 3.0)).id();
+    let ignored = world
+        .spawn((RerunIgnore, Transform::default()))
+        .with_child(Transform::default())
+        .id();
+    app.step(2);
+
+    let world = app.app.world_mut();
+    assert!(world.get::<RerunEntityPath>(entity).is_some());
+    assert!(world.get::<RerunEntityPath>(ignored).is_none());
+    world.entity_mut(entity).insert(RerunIgnore);
+    app.step(2);
+
+    app.app
+        .world_mut()
+        .entity_mut(entity)
+        .remove::<RerunIgnore>();
+    app.step(2);
+
+    let capture = app.capture();
+    let entity_path = format!("world/{entity:?}");
+
+    // Logged when spawned, cleared when ignored, logged again as-is when un-ignored.
+    assert_eq!(
+        capture
+            .values(&entity_path, "rerun.components.ClearIsRecursive")
+            .map(|values| values.len()),
+        Some(1)
+    );
+    assert_eq!(
+        capture
+            .values(&entity_path, "rerun.components.Translation3D")
+            .map(|values| values.len()),
+        Some(2)
+    );
+    assert!(capture
+        .entity_paths()
+        .all(|entity_path| !entity_path.contains(&format!("{ignored:?}"))));
+}
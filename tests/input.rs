@@ -0,0 +1,209 @@
+use std::time::Duration;
+
+use bevy::{
+    input::{
+        gamepad::{
+            GamepadConnection, GamepadConnectionEvent, RawGamepadButtonChangedEvent,
+            RawGamepadEvent,
+        },
+        keyboard::KeyboardInput,
+        mouse::MouseButtonInput,
+        ButtonState, InputPlugin, InputSystem,
+    },
+    prelude::*,
+    time::TimeUpdateStrategy,
+    utils::HashSet,
+};
+use revy::{testing::RerunTestApp, RerunInputReplayPlugin, RerunPlugin};
+
+// ---
+
+fn key(key_code: KeyCode, state: ButtonState) -> KeyboardInput {
+    KeyboardInput {
+        key_code,
+        logical_key: bevy::input::keyboard::Key::Unidentified(
+            bevy::input::keyboard::NativeKey::Unidentified,
+        ),
+        state,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    }
+}
+
+fn mouse(button: MouseButton, state: ButtonState) -> MouseButtonInput {
+    MouseButtonInput {
+        button,
+        state,
+        window: Entity::PLACEHOLDER,
+    }
+}
+
+/// The keys and mouse buttons that are currently pressed.
+fn pressed(app: &App) -> (HashSet<KeyCode>, HashSet<MouseButton>) {
+    let world = app.world();
+    (
+        world
+            .resource::<ButtonInput<KeyCode>>()
+            .get_pressed()
+            .copied()
+            .collect(),
+        world
+            .resource::<ButtonInput<MouseButton>>()
+            .get_pressed()
+            .copied()
+            .collect(),
+    )
+}
+
+#[test]
+fn capture_and_replay_inputs() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("capture_and_replay.rrd");
+
+    let inputs = [
+        vec![
+            key(KeyCode::KeyA, ButtonState::Pressed),
+            key(KeyCode::KeyB, ButtonState::Pressed),
+        ],
+        vec![],
+        vec![key(KeyCode::KeyA, ButtonState::Released)],
+        vec![],
+    ];
+    let mouse_inputs = [
+        vec![],
+        vec![mouse(MouseButton::Left, ButtonState::Pressed)],
+        vec![],
+        vec![mouse(MouseButton::Left, ButtonState::Released)],
+    ];
+
+    let mut recorder = RerunTestApp::with_plugin(|rec| RerunPlugin::new(rec).with_input_capture());
+    recorder
+        .app
+        .add_plugins(InputPlugin)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+
+    let mut recorded = Vec::new();
+    for (keys, buttons) in inputs.into_iter().zip(mouse_inputs) {
+        let world = recorder.app.world_mut();
+        world.send_event_batch(keys);
+        world.send_event_batch(buttons);
+        recorder.step(1);
+        recorded.push(pressed(&recorder.app));
+    }
+    recorder.save(&path).unwrap();
+
+    let mut replayer = RerunTestApp::new();
+    replayer
+        .app
+        .add_plugins((InputPlugin, RerunInputReplayPlugin::new(&path)));
+
+    let mut replayed = Vec::new();
+    for _ in 0..recorded.len() {
+        replayer.step(1);
+        replayed.push(pressed(&replayer.app));
+    }
+    assert!(recorded
+        .iter()
+        .any(|(keys, buttons)| !keys.is_empty() && !buttons.is_empty()));
+    assert_eq!(recorded, replayed);
+
+    // The recorded frame deltas only apply for as long as the replay lasts.
+    replayer.step(1);
+    let strategy = replayer.app.world().resource::<TimeUpdateStrategy>();
+    assert!(matches!(
+        strategy,
+        TimeUpdateStrategy::ManualDuration(delta) if *delta == Duration::from_secs_f64(1.0 / 60.0)
+    ));
+
+    std::fs::remove_file(&path).ok();
+}
+
+/// The gamepad buttons that are currently pressed, if the gamepad is connected.
+fn pressed_buttons(app: &App, gamepad: Entity) -> Option<HashSet<GamepadButton>> {
+    app.world()
+        .get::<Gamepad>(gamepad)
+        .map(|gamepad| gamepad.get_pressed().copied().collect())
+}
+
+/// Sends a real gamepad event every frame, in the same spot as gilrs does.
+fn system_fake_gilrs(
+    gamepads: Query<'_, '_, Entity, With<Gamepad>>,
+    mut events: EventWriter<'_, RawGamepadEvent>,
+) {
+    for gamepad in &gamepads {
+        events.send(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+            gamepad,
+            GamepadButton::North,
+            1.0,
+        )));
+    }
+}
+
+#[test]
+fn capture_and_replay_gamepad() {
+    let path =
+        std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("capture_and_replay_gamepad.rrd");
+
+    let button = |gamepad, value| {
+        RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+            gamepad,
+            GamepadButton::South,
+            value,
+        ))
+    };
+
+    let mut recorder = RerunTestApp::with_plugin(|rec| RerunPlugin::new(rec).with_input_capture());
+    recorder.app.add_plugins(InputPlugin);
+    let gamepad = recorder.app.world_mut().spawn_empty().id();
+
+    let connection = GamepadConnectionEvent::new(
+        gamepad,
+        GamepadConnection::Connected {
+            name: "pad".into(),
+            vendor_id: None,
+            product_id: None,
+        },
+    );
+    let inputs = [
+        (
+            vec![RawGamepadEvent::Connection(connection.clone())],
+            Some(connection),
+        ),
+        (vec![button(gamepad, 1.0)], None),
+        (vec![], None),
+        (vec![button(gamepad, 0.0)], None),
+    ];
+
+    let mut recorded = Vec::new();
+    for (events, connection) in inputs {
+        // NOTE: Just like gilrs, connections are sent both as raw and as connection events.
+        let world = recorder.app.world_mut();
+        world.send_event_batch(events);
+        world.send_event_batch(connection);
+        recorder.step(1);
+        recorded.push(pressed_buttons(&recorder.app, gamepad));
+    }
+    recorder.save(&path).unwrap();
+
+    let mut replayer = RerunTestApp::new();
+    replayer
+        .app
+        // NOTE: Added first, so that it would run after the replay if they weren't ordered.
+        .add_systems(PreUpdate, system_fake_gilrs.before(InputSystem))
+        .add_plugins((InputPlugin, RerunInputReplayPlugin::new(&path)));
+    // NOTE: The recorded events refer to the gamepad by entity id.
+    assert_eq!(replayer.app.world_mut().spawn_empty().id(), gamepad);
+
+    let mut replayed = Vec::new();
+    for _ in 0..recorded.len() {
+        replayer.step(1);
+        replayed.push(pressed_buttons(&replayer.app, gamepad));
+    }
+    assert!(recorded
+        .iter()
+        .any(|buttons| buttons.as_ref().is_some_and(|buttons| !buttons.is_empty())));
+    assert_eq!(recorded, replayed);
+
+    std::fs::remove_file(&path).ok();
+}
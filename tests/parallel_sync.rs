@@ -0,0 +1,50 @@
+use bevy::{
+    prelude::*,
+    tasks::{ComputeTaskPool, TaskPoolBuilder},
+};
+use revy::testing::RerunTestApp;
+
+// ---
+
+fn system_move(mut transforms: Query<'_, '_, &mut Transform>) {
+    for mut transform in &mut transforms {
+        transform.translation.x += 1.0;
+    }
+}
+
+#[test]
+fn parallel_sync() {
+    // NOTE: The task pools are global: this must happen before any app gets to initialize them,
+    // hence why this test lives in its own binary.
+    ComputeTaskPool::get_or_init(|| TaskPoolBuilder::new().num_threads(4).build());
+
+    // NOTE: `sim_time` is enabled by default.
+    let mut app = RerunTestApp::new();
+    app.app.add_systems(Update, system_move);
+
+    // Enough entities to be split into many batches, synced from many threads.
+    let entities = (0..1000)
+        .map(|_| app.app.world_mut().spawn(Transform::default()).id())
+        .collect::<Vec<_>>();
+    app.step(5);
+
+    let capture = app.capture();
+    let expected_sim_times = capture
+        .times(&format!("world/{:?}", entities[0]), "sim_time")
+        .collect::<Vec<_>>();
+    assert_eq!(expected_sim_times.len(), 5);
+
+    for entity in entities {
+        let entity_path = format!("world/{entity:?}");
+        assert_eq!(
+            capture.times(&entity_path, "frame").collect::<Vec<_>>(),
+            (0..5).collect::<Vec<_>>(),
+            "{entity_path}"
+        );
+        assert_eq!(
+            capture.times(&entity_path, "sim_time").collect::<Vec<_>>(),
+            expected_sim_times,
+            "{entity_path}"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use revy::{testing::RerunTestApp, RerunReplayPlugin, RerunReplayRequest, RerunReplayTime};
+
+// ---
+
+#[derive(Component, Reflect, Default, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+struct Health(u32);
+
+#[test]
+fn record_and_replay() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("record_and_replay.rrd");
+
+    let mut recorder = RerunTestApp::new();
+    recorder.app.register_type::<Health>();
+    let world = recorder.app.world_mut();
+    let player = world
+        .spawn((Transform::from_xyz(1.0, 2.0, 3.0), Health(100)))
+        .with_child((Transform::from_xyz(0.0, 1.0, 0.0), Health(10)))
+        .id();
+    let enemy = world.spawn((Transform::default(), Health(20))).id();
+    recorder.step(2); // frames 0 and 1
+
+    let world = recorder.app.world_mut();
+    world
+        .entity_mut(player)
+        .insert((Transform::from_xyz(4.0, 5.0, 6.0), Health(50)));
+    world.entity_mut(enemy).despawn();
+    recorder.step(2); // frames 2 and 3
+
+    recorder.save(&path).unwrap();
+
+    let mut replayer = RerunTestApp::new();
+    replayer
+        .app
+        .register_type::<Health>()
+        .add_plugins(RerunReplayPlugin::new(&path).at(RerunReplayTime::Frame(1)));
+    replayer.step(1);
+
+    let healths = |app: &mut App| {
+        let world = app.world_mut();
+        let mut entities = world.query::<(&Health, &Transform, Option<&Parent>)>();
+        let mut healths = entities
+            .iter(world)
+            .map(|(health, transform, parent)| (health.0, transform.translation, parent.is_some()))
+            .collect::<Vec<_>>();
+        healths.sort_by_key(|(health, _, _)| *health);
+        healths
+    };
+
+    assert_eq!(
+        healths(&mut replayer.app),
+        [
+            (10, Vec3::new(0.0, 1.0, 0.0), true),
+            (20, Vec3::ZERO, false),
+            (100, Vec3::new(1.0, 2.0, 3.0), false),
+        ]
+    );
+
+    replayer
+        .app
+        .world_mut()
+        .send_event(RerunReplayRequest(RerunReplayTime::Frame(3)));
+    replayer.step(1);
+
+    assert_eq!(
+        healths(&mut replayer.app),
+        [
+            (10, Vec3::new(0.0, 1.0, 0.0), true),
+            (50, Vec3::new(4.0, 5.0, 6.0), false),
+        ]
+    );
+
+    // Way past the end of the recording: same as its last frame.
+    replayer
+        .app
+        .world_mut()
+        .send_event(RerunReplayRequest(RerunReplayTime::SimTime(60.0)));
+    replayer.step(1);
+
+    assert_eq!(
+        healths(&mut replayer.app),
+        [
+            (10, Vec3::new(0.0, 1.0, 0.0), true),
+            (50, Vec3::new(4.0, 5.0, 6.0), false),
+        ]
+    );
+
+    std::fs::remove_file(&path).ok();
+}
+
+#[derive(Component)]
+struct Untracked;
+
+#[test]
+fn dead_parent_alive_child() {
+    use revy::RerunPlugin;
+
+    let path =
+        std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("dead_parent_alive_child.rrd");
+
+    let mut recorder =
+        RerunTestApp::with_plugin(|rec| RerunPlugin::new(rec).with_filter::<Without<Untracked>>());
+    recorder.app.register_type::<Health>();
+    let world = recorder.app.world_mut();
+    let parent = world
+        .spawn((Transform::default(), Health(100)))
+        .with_child((Transform::from_xyz(0.0, 1.0, 0.0), Health(10)))
+        .id();
+    recorder.step(2); // frames 0 and 1
+
+    // The parent stops being synced, while its child still is.
+    recorder
+        .app
+        .world_mut()
+        .entity_mut(parent)
+        .insert(Untracked);
+    recorder.step(2); // frames 2 and 3
+
+    recorder.save(&path).unwrap();
+
+    let mut replayer = RerunTestApp::new();
+    replayer
+        .app
+        .register_type::<Health>()
+        .add_plugins(RerunReplayPlugin::new(&path).at(RerunReplayTime::Frame(1)));
+    replayer.step(1);
+
+    let healths = |app: &mut App| {
+        let world = app.world_mut();
+        let mut entities = world.query::<(Entity, &Health, Option<&Parent>)>();
+        let mut healths = entities
+            .iter(world)
+            .map(|(entity, health, parent)| (health.0, entity, parent.is_some()))
+            .collect::<Vec<_>>();
+        healths.sort_by_key(|(health, _, _)| *health);
+        healths
+    };
+
+    let [(10, child, true), (100, _, false)] = healths(&mut replayer.app)[..] else {
+        panic!("expected a parent and its child");
+    };
+
+    replayer
+        .app
+        .world_mut()
+        .send_event(RerunReplayRequest(RerunReplayTime::Frame(3)));
+    replayer.step(1);
+
+    // The child outlives its parent, rather than being despawned along with it.
+    assert_eq!(healths(&mut replayer.app), [(10, child, false)]);
+
+    std::fs::remove_file(&path).ok();
+}
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use revy::{testing::RerunTestApp, RerunPlugin, RerunTimelines};
+
+// ---
+
+fn system_move(mut transforms: Query<'_, '_, &mut Transform>) {
+    for mut transform in &mut transforms {
+        transform.translation.x += 1.0;
+    }
+}
+
+#[test]
+fn all_timelines() {
+    let mut app = RerunTestApp::with_plugin(|rec| {
+        RerunPlugin::new(rec).with_timelines(RerunTimelines::all())
+    });
+    app.app
+        .insert_resource(Time::<Fixed>::from_hz(30.0))
+        .add_systems(Update, system_move);
+
+    let entity = app.app.world_mut().spawn(Transform::default()).id();
+    app.step(5);
+    app.app.world_mut().resource_mut::<Time<Virtual>>().pause();
+    app.step(5);
+
+    let capture = app.capture();
+    let entity_path = format!("world/{entity:?}");
+    let times = |timeline| capture.times(&entity_path, timeline).collect::<Vec<_>>();
+
+    let timelines = capture.timelines(&entity_path).collect::<Vec<_>>();
+    for timeline in [
+        "fixed_tick",
+        "frame",
+        "real_time",
+        "sim_time",
+        "virtual_time",
+    ] {
+        assert!(timelines.contains(&timeline), "{timeline} in {timelines:?}");
+    }
+
+    assert_eq!(times("frame"), (0..10).collect::<Vec<_>>());
+
+    // Fixed updates run at half the frame rate, and stop along with `Time<Virtual>`.
+    assert_eq!(times("fixed_tick"), vec![0, 1, 2]);
+
+    // Pausing `Time<Virtual>` stops `virtual_time` (and `sim_time`, which follows it), but not
+    // `real_time`.
+    assert_eq!(times("real_time").len(), 10);
+    assert!(times("virtual_time").len() < 10);
+    assert_eq!(times("sim_time"), times("virtual_time"));
+}
+
+#[derive(Component)]
+struct FixedMover;
+
+fn system_fixed_move(mut transforms: Query<'_, '_, &mut Transform, With<FixedMover>>) {
+    for mut transform in &mut transforms {
+        transform.translation.y += 1.0;
+    }
+}
+
+#[test]
+fn fixed_sync_point() {
+    use revy::{external::rerun, RerunSyncPoint};
+
+    let mut app = RerunTestApp::with_plugin(|rec| {
+        RerunPlugin::new(rec).with_sync_point(RerunSyncPoint::new(FixedPostUpdate, "fixed_sync"))
+    });
+    app.app
+        .insert_resource(Time::<Fixed>::from_hz(20.0))
+        .add_systems(FixedUpdate, system_fixed_move)
+        .add_systems(Update, system_move);
+
+    let fixed = app
+        .app
+        .world_mut()
+        .spawn((FixedMover, Transform::default()))
+        .id();
+    app.step(12);
+
+    let capture = app.capture();
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("fixed_sync_point.rrd");
+    app.save(&path).unwrap();
+
+    // One sync per fixed update, each indexed by the next step of the sequence.
+    let fixed_path = format!("world/{fixed:?}");
+    let steps = capture.times(&fixed_path, "fixed_sync").collect::<Vec<_>>();
+    assert!(steps.len() >= 3, "{steps:?}");
+    assert_eq!(steps, (0..steps.len() as i64).collect::<Vec<_>>());
+
+    // `Update` moves the entity every frame, which only the `Last` sync gets to see: that data
+    // must not be indexed by `fixed_sync`.
+    let file = std::io::BufReader::new(std::fs::File::open(path).unwrap());
+    let decoder = rerun::external::re_log_encoding::decoder::Decoder::new(
+        rerun::external::re_log_encoding::VersionPolicy::Error,
+        file,
+    )
+    .unwrap();
+    let frame = rerun::Timeline::new_sequence("frame");
+    let mut frames_without_fixed_sync = std::collections::BTreeSet::new();
+    for msg in decoder {
+        let rerun::log::LogMsg::ArrowMsg(_, msg) = msg.unwrap() else {
+            continue;
+        };
+        let chunk = rerun::log::Chunk::from_arrow_msg(&msg).unwrap();
+        if chunk.entity_path() != &rerun::EntityPath::from(fixed_path.as_str()) {
+            continue;
+        }
+        if !chunk
+            .timelines()
+            .keys()
+            .any(|timeline| timeline.name() == "fixed_sync")
+        {
+            frames_without_fixed_sync
+                .extend(chunk.iter_indices(&frame).map(|(time, _)| time.as_i64()));
+        }
+    }
+    assert_eq!(
+        frames_without_fixed_sync.into_iter().collect::<Vec<_>>(),
+        (0..12).collect::<Vec<_>>()
+    );
+}